@@ -1,44 +1,435 @@
-use std::marker::PhantomData;
-use random_utils::RandomUtils;
-use chromosome::Chromosome;
-
-pub trait Breeding<RU: RandomUtils> {
-    fn generate_chromosome() -> Chromosome;
-    fn conception(first_parent: &Chromosome, second_parent: &Chromosome, child: &mut Chromosome);
-    fn attempt_cross_zygotes(chr: &mut Chromosome);
-    fn attempt_mutate(chr: &mut Chromosome);
+use random_utils::{RandomUtils, GaRng};
+use std;
+use chromosome::{Chromosome, Gamete, ZygoteKind};
+use haploid_chromosome::HaploidChromosome;
+use genome::Genome;
+use genotype::{Genotype, LinearGenotype};
+use ga_config::{GaConfig, Crossover, Reproduction};
+
+pub trait Breeding<RU: RandomUtils, G: Genotype = Chromosome> {
+    fn generate_chromosome(&mut self, config: &GaConfig, rng: &mut GaRng) -> G;
+    fn conception(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &G,
+        second_parent: &G,
+        child: &mut G,
+        rng: &mut GaRng,
+    );
+    fn conception_pair(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &G,
+        second_parent: &G,
+        first_child: &mut G,
+        second_child: &mut G,
+        rng: &mut GaRng,
+    );
+    fn attempt_cross_zygotes(&mut self, config: &GaConfig, chr: &mut G, rng: &mut GaRng);
+    fn attempt_mutate(&mut self, config: &GaConfig, chr: &mut G, rng: &mut GaRng);
 }
 
 pub struct BreedingStruct<RU: RandomUtils> {
-    phantom: PhantomData<RU>,
+    random_utils: RU,
+    crossing_masks: Vec<u64>,
+    crossing_points: Vec<usize>,
+    crossing_segment: (usize, usize),
+    chromosome_configs: Vec<GaConfig>,
+    chromosome_configs_source: Option<GaConfig>,
 }
 
-impl<RU: RandomUtils> Breeding<RU> for BreedingStruct<RU> {
-    fn generate_chromosome() -> Chromosome {
-        Chromosome::new(RU::generate_zygote(), RU::generate_zygote())
+impl<RU: RandomUtils> BreedingStruct<RU> {
+    pub fn new(random_utils: RU) -> Self {
+        Self {
+            random_utils,
+            crossing_masks: Vec::new(),
+            crossing_points: Vec::new(),
+            crossing_segment: (0, 0),
+            chromosome_configs: Vec::new(),
+            chromosome_configs_source: None,
+        }
+    }
+
+    fn take_chromosome_configs(&mut self, config: &GaConfig) -> Vec<GaConfig> {
+        if self.chromosome_configs_source.as_ref() != Some(config) {
+            self.chromosome_configs = config
+                .genome_genes_amounts()
+                .iter()
+                .map(|genes_amount| {
+                    let mut chromosome_config = config.clone();
+                    chromosome_config.chromosome_genes_amount = *genes_amount;
+                    chromosome_config.genome_genes_amounts = Vec::new();
+                    chromosome_config
+                })
+                .collect();
+            self.chromosome_configs_source = Some(config.clone());
+        }
+        std::mem::take(&mut self.chromosome_configs)
+    }
+
+    fn draw_crossing(&mut self, config: &GaConfig, u64s_amount: usize, rng: &mut GaRng) {
+        match config.crossover {
+            Crossover::Segment => {
+                let begin = self.random_utils.crossing_chromosome_pos(config, rng);
+                let amount = self.random_utils.crossing_segment_length(config, rng);
+                self.crossing_segment = (begin, amount);
+            }
+            Crossover::Uniform => {
+                self.crossing_masks.clear();
+                for _ in 0..u64s_amount {
+                    let mask = self.random_utils.crossing_mask(rng);
+                    self.crossing_masks.push(mask);
+                }
+            }
+            Crossover::KPoint(k) => {
+                self.crossing_points.clear();
+                for _ in 0..k {
+                    let point = self.random_utils.crossing_chromosome_pos(config, rng);
+                    self.crossing_points.push(point);
+                }
+                self.crossing_points.sort();
+            }
+        }
+    }
+
+    fn draw_gamete(&mut self, config: &GaConfig, rng: &mut GaRng) -> Gamete {
+        let from_recessive = self.random_utils.gamete_from_recessive(rng);
+        if self.random_utils.should_cross_chromosomes(config, rng) {
+            let begin = self.random_utils.crossing_chromosome_pos(config, rng);
+            let amount = self.random_utils.crossing_segment_length(config, rng);
+            Gamete {
+                from_recessive,
+                begin,
+                amount,
+            }
+        } else {
+            Gamete {
+                from_recessive,
+                begin: 0,
+                amount: 0,
+            }
+        }
+    }
+
+    fn should_mutate_with_rate(&mut self, config: &GaConfig, rate: f64, rng: &mut GaRng) -> bool {
+        rate > 0.0 && self.random_utils.should_mutate_with_rate(config, rate, rng)
+    }
+
+    fn mutation_range(&mut self, config: &GaConfig, rng: &mut GaRng) -> (usize, usize) {
+        let first = self.random_utils.mutation_pos(config, rng);
+        let second = self.random_utils.mutation_pos(config, rng);
+        if first < second {
+            (first, second + 1)
+        } else {
+            (second, first + 1)
+        }
+    }
+
+    fn apply_crossing<G: LinearGenotype>(&self, config: &GaConfig, second_parent: &G, child: &mut G) {
+        match config.crossover {
+            Crossover::Segment => {
+                let (begin, amount) = self.crossing_segment;
+                child.cross_chromosomes(second_parent, begin, amount);
+            }
+            Crossover::Uniform => {
+                child.cross_chromosomes_uniform(second_parent, &self.crossing_masks);
+            }
+            Crossover::KPoint(_) => {
+                child.cross_chromosomes_k_point(second_parent, &self.crossing_points);
+            }
+        }
     }
 
-    fn conception(first_parent: &Chromosome, second_parent: &Chromosome, child: &mut Chromosome) {
+    fn cross_parents<G: LinearGenotype>(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &G,
+        second_parent: &G,
+        child: &mut G,
+        rng: &mut GaRng,
+    ) {
         child.overwrite(first_parent);
 
-        child.cross_chromosomes(
+        if self.random_utils.should_cross_chromosomes(config, rng) {
+            self.draw_crossing(config, child.u64s_amount(), rng);
+            self.apply_crossing(config, second_parent, child);
+        }
+    }
+
+    fn cross_parents_pair<G: LinearGenotype>(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &G,
+        second_parent: &G,
+        first_child: &mut G,
+        second_child: &mut G,
+        rng: &mut GaRng,
+    ) {
+        first_child.overwrite(first_parent);
+        second_child.overwrite(second_parent);
+
+        if self.random_utils.should_cross_chromosomes(config, rng) {
+            self.draw_crossing(config, first_child.u64s_amount(), rng);
+            self.apply_crossing(config, second_parent, first_child);
+            self.apply_crossing(config, first_parent, second_child);
+        }
+    }
+
+    fn attempt_rearrange<G: LinearGenotype>(&mut self, config: &GaConfig, chr: &mut G, rng: &mut GaRng) {
+        if self.should_mutate_with_rate(config, config.value_flip_rate, rng) {
+            let pos = self.random_utils.mutation_pos(config, rng);
+            chr.flip_value(pos);
+        }
+        if self.should_mutate_with_rate(config, config.inversion_rate, rng) {
+            let (begin, end) = self.mutation_range(config, rng);
+            chr.invert(begin, end);
+        }
+        if self.should_mutate_with_rate(config, config.swap_rate, rng) {
+            let first = self.random_utils.mutation_pos(config, rng);
+            let second = self.random_utils.mutation_pos(config, rng);
+            chr.swap(first, second);
+        }
+        if self.should_mutate_with_rate(config, config.scramble_rate, rng) {
+            let (begin, end) = self.mutation_range(config, rng);
+            let order = self.random_utils.scramble_order(end - begin, rng);
+            chr.scramble(begin, &order);
+        }
+    }
+}
+
+impl<RU: RandomUtils> Breeding<RU> for BreedingStruct<RU> {
+    fn generate_chromosome(&mut self, config: &GaConfig, rng: &mut GaRng) -> Chromosome {
+        let dominant = self.random_utils.generate_zygote(config, rng);
+        let recessive = self.random_utils.generate_zygote(config, rng);
+        Chromosome::new(dominant, recessive)
+    }
+
+    fn conception(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &Chromosome,
+        second_parent: &Chromosome,
+        child: &mut Chromosome,
+        rng: &mut GaRng,
+    ) {
+        if let Reproduction::Meiosis = config.reproduction {
+            let first_gamete = self.draw_gamete(config, rng);
+            let second_gamete = self.draw_gamete(config, rng);
+            child.inherit_gametes(first_parent, &first_gamete, second_parent, &second_gamete);
+            self.attempt_mutate(config, child, rng);
+            self.attempt_cross_zygotes(config, child, rng);
+            return;
+        }
+        self.cross_parents(config, first_parent, second_parent, child, rng);
+        self.attempt_mutate(config, child, rng);
+        self.attempt_cross_zygotes(config, child, rng);
+    }
+
+    fn conception_pair(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &Chromosome,
+        second_parent: &Chromosome,
+        first_child: &mut Chromosome,
+        second_child: &mut Chromosome,
+        rng: &mut GaRng,
+    ) {
+        if let Reproduction::Meiosis = config.reproduction {
+            let first_gamete = self.draw_gamete(config, rng);
+            let second_gamete = self.draw_gamete(config, rng);
+            first_child.inherit_gametes(first_parent, &first_gamete, second_parent, &second_gamete);
+            second_child.inherit_gametes(
+                first_parent,
+                &first_gamete.complement(),
+                second_parent,
+                &second_gamete.complement(),
+            );
+            self.attempt_mutate(config, first_child, rng);
+            self.attempt_cross_zygotes(config, first_child, rng);
+            self.attempt_mutate(config, second_child, rng);
+            self.attempt_cross_zygotes(config, second_child, rng);
+            return;
+        }
+        self.cross_parents_pair(
+            config,
+            first_parent,
             second_parent,
-            RU::crossing_chromosome_pos(),
-            RU::crossing_chromosome_pos(),
+            first_child,
+            second_child,
+            rng,
         );
-        Self::attempt_mutate(child);
-        Self::attempt_cross_zygotes(child);
+        self.attempt_mutate(config, first_child, rng);
+        self.attempt_cross_zygotes(config, first_child, rng);
+        self.attempt_mutate(config, second_child, rng);
+        self.attempt_cross_zygotes(config, second_child, rng);
+    }
+
+    fn attempt_cross_zygotes(&mut self, config: &GaConfig, chr: &mut Chromosome, rng: &mut GaRng) {
+        if self.random_utils.should_cross_zygotes(config, rng) {
+            let begin = self.random_utils.crossing_zygote_pos(config, rng);
+            let amount = self.random_utils.crossing_zygote_pos(config, rng) + 1;
+            chr.cross_zygotes(begin, amount);
+        };
     }
 
-    fn attempt_cross_zygotes(chr: &mut Chromosome) {
-        if RU::should_cross_zygotes() {
-            chr.cross_zygotes(RU::crossing_zygote_pos(), RU::crossing_zygote_pos() + 1);
+    fn attempt_mutate(&mut self, config: &GaConfig, chr: &mut Chromosome, rng: &mut GaRng) {
+        if self.random_utils.should_mutate(config, rng) {
+            let pos = self.random_utils.mutation_pos(config, rng);
+            let gen = self.random_utils.rand_gen(rng);
+            chr.mutate(ZygoteKind::Dominant, pos, &gen);
         };
+        if self.should_mutate_with_rate(config, config.recessive_mutation_rate, rng) {
+            let pos = self.random_utils.mutation_pos(config, rng);
+            let gen = self.random_utils.rand_gen(rng);
+            chr.mutate(ZygoteKind::Recessive, pos, &gen);
+        }
+        if self.should_mutate_with_rate(config, config.dominance_flip_rate, rng) {
+            let pos = self.random_utils.mutation_pos(config, rng);
+            chr.flip_dominance(pos);
+        }
+        self.attempt_rearrange(config, chr, rng);
+    }
+}
+
+/// Haploid chromosomes have no second zygote and no dominance, so zygote crossing,
+/// meiosis, recessive mutation and dominance flips are skipped.
+impl<RU: RandomUtils> Breeding<RU, HaploidChromosome> for BreedingStruct<RU> {
+    fn generate_chromosome(&mut self, config: &GaConfig, rng: &mut GaRng) -> HaploidChromosome {
+        let zygote = self.random_utils.generate_zygote(config, rng);
+        HaploidChromosome::new(zygote.into_values())
+    }
+
+    fn conception(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &HaploidChromosome,
+        second_parent: &HaploidChromosome,
+        child: &mut HaploidChromosome,
+        rng: &mut GaRng,
+    ) {
+        self.cross_parents(config, first_parent, second_parent, child, rng);
+        self.attempt_mutate(config, child, rng);
+    }
+
+    fn conception_pair(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &HaploidChromosome,
+        second_parent: &HaploidChromosome,
+        first_child: &mut HaploidChromosome,
+        second_child: &mut HaploidChromosome,
+        rng: &mut GaRng,
+    ) {
+        self.cross_parents_pair(
+            config,
+            first_parent,
+            second_parent,
+            first_child,
+            second_child,
+            rng,
+        );
+        self.attempt_mutate(config, first_child, rng);
+        self.attempt_mutate(config, second_child, rng);
     }
 
-    fn attempt_mutate(chr: &mut Chromosome) {
-        if RU::should_mutate() {
-            chr.mutate(RU::mutation_pos(), &RU::rand_gen());
+    fn attempt_cross_zygotes(
+        &mut self,
+        _config: &GaConfig,
+        _chr: &mut HaploidChromosome,
+        _rng: &mut GaRng,
+    ) {
+    }
+
+    fn attempt_mutate(&mut self, config: &GaConfig, chr: &mut HaploidChromosome, rng: &mut GaRng) {
+        if self.random_utils.should_mutate(config, rng) {
+            let pos = self.random_utils.mutation_pos(config, rng);
+            let gen = self.random_utils.rand_gen(rng);
+            chr.mutate(pos, &gen);
         };
+        self.attempt_rearrange(config, chr, rng);
+    }
+}
+
+/// Every chromosome of a genome is bred on its own with a config carrying its gene count.
+/// Chromosomes assort independently: each one of a child takes either parent as the first one.
+impl<RU: RandomUtils> Breeding<RU, Genome> for BreedingStruct<RU> {
+    fn generate_chromosome(&mut self, config: &GaConfig, rng: &mut GaRng) -> Genome {
+        let configs = self.take_chromosome_configs(config);
+        let chromosomes = configs
+            .iter()
+            .map(|c| Breeding::<RU, Chromosome>::generate_chromosome(self, c, rng))
+            .collect();
+        self.chromosome_configs = configs;
+        Genome::new(chromosomes)
+    }
+
+    fn conception(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &Genome,
+        second_parent: &Genome,
+        child: &mut Genome,
+        rng: &mut GaRng,
+    ) {
+        let configs = self.take_chromosome_configs(config);
+        for (pos, chromosome_config) in configs.iter().enumerate() {
+            let (first, second) = if self.random_utils.chromosome_from_second_parent(rng) {
+                (&second_parent.chromosomes[pos], &first_parent.chromosomes[pos])
+            } else {
+                (&first_parent.chromosomes[pos], &second_parent.chromosomes[pos])
+            };
+            self.conception(
+                chromosome_config,
+                first,
+                second,
+                &mut child.chromosomes[pos],
+                rng,
+            );
+        }
+        self.chromosome_configs = configs;
+    }
+
+    fn conception_pair(
+        &mut self,
+        config: &GaConfig,
+        first_parent: &Genome,
+        second_parent: &Genome,
+        first_child: &mut Genome,
+        second_child: &mut Genome,
+        rng: &mut GaRng,
+    ) {
+        let configs = self.take_chromosome_configs(config);
+        for (pos, chromosome_config) in configs.iter().enumerate() {
+            let (first, second) = if self.random_utils.chromosome_from_second_parent(rng) {
+                (&second_parent.chromosomes[pos], &first_parent.chromosomes[pos])
+            } else {
+                (&first_parent.chromosomes[pos], &second_parent.chromosomes[pos])
+            };
+            self.conception_pair(
+                chromosome_config,
+                first,
+                second,
+                &mut first_child.chromosomes[pos],
+                &mut second_child.chromosomes[pos],
+                rng,
+            );
+        }
+        self.chromosome_configs = configs;
+    }
+
+    fn attempt_cross_zygotes(&mut self, config: &GaConfig, genome: &mut Genome, rng: &mut GaRng) {
+        let configs = self.take_chromosome_configs(config);
+        for (chromosome, chromosome_config) in genome.chromosomes.iter_mut().zip(&configs) {
+            self.attempt_cross_zygotes(chromosome_config, chromosome, rng);
+        }
+        self.chromosome_configs = configs;
+    }
+
+    fn attempt_mutate(&mut self, config: &GaConfig, genome: &mut Genome, rng: &mut GaRng) {
+        let configs = self.take_chromosome_configs(config);
+        for (chromosome, chromosome_config) in genome.chromosomes.iter_mut().zip(&configs) {
+            self.attempt_mutate(chromosome_config, chromosome, rng);
+        }
+        self.chromosome_configs = configs;
     }
 }
@@ -1,12 +1,45 @@
 use zygote::Zygote;
 use std::fmt;
 use gen::Gen;
+use genotype::{Genotype, LinearGenotype, DecodedChanges};
+use dominance::{self, DominanceRule};
+use random_utils::GaRng;
+use std;
 
+/// Describes how a parent recombines its zygotes into a gamete: the zygote taken as a base
+/// and the segment copied into it from the other one.
+#[derive(Clone, Debug)]
+pub struct Gamete {
+    pub from_recessive: bool,
+    pub begin: usize,
+    pub amount: usize,
+}
+
+impl Gamete {
+    pub fn complement(&self) -> Gamete {
+        Gamete {
+            from_recessive: !self.from_recessive,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ZygoteKind {
+    Dominant,
+    Recessive,
+}
+
+/// Keeps track of words changed since the last decoding, so only those are decoded again.
 #[derive(Clone)]
 pub struct Chromosome {
     dominant: Zygote,
     recessive: Zygote,
     pub decoded: Vec<u64>,
+    dirty: Vec<bool>,
+    dirty_words: Vec<usize>,
+    changed_words: Vec<usize>,
+    previous_words: Vec<u64>,
 }
 
 impl fmt::Display for Chromosome {
@@ -23,50 +56,199 @@ impl fmt::Debug for Chromosome {
 
 impl Chromosome {
     pub fn new(dominant: Zygote, recessive: Zygote) -> Self {
+        let u64s_amount = dominant.u64s_amount();
+        Self::with_decoded_len(dominant, recessive, u64s_amount)
+    }
+
+    fn with_decoded_len(dominant: Zygote, recessive: Zygote, decoded_len: usize) -> Self {
+        let u64s_amount = dominant.u64s_amount();
         Self {
-            decoded: vec![0;dominant.u64s_amount()],
             dominant,
             recessive,
+            decoded: vec![0; decoded_len],
+            dirty: vec![true; u64s_amount],
+            dirty_words: (0..u64s_amount).collect(),
+            changed_words: Vec::new(),
+            previous_words: Vec::new(),
         }
     }
 
-    pub fn overwrite(&mut self, source: &Chromosome) {
-        self.dominant.overwrite(&source.dominant);
-        self.recessive.overwrite(&source.recessive);
+    #[allow(dead_code)]
+    fn from_strings(dominant: &str, recessive: &str) -> Chromosome {
+        Chromosome::with_decoded_len(
+            dominant.parse::<Zygote>().unwrap(),
+            recessive.parse::<Zygote>().unwrap(),
+            dominant.len(),
+        )
     }
-
-    pub fn decode_genotype(&mut self) {
-        let mut p = 0;
-        while p < self.dominant.u64s_amount() {
-            let dd = self.dominant.get_d_u64(p);
-            let dv = self.dominant.get_v_u64(p);
-            let rd = self.recessive.get_d_u64(p);
-            let rv = self.recessive.get_v_u64(p);
-            self.decoded[p] = dv & !rd | rd & rv & !dd | dd & dv;
-            p += 1
+    fn mark_dirty(&mut self, p: usize) {
+        if !self.dirty[p] {
+            self.dirty[p] = true;
+            self.dirty_words.push(p);
         }
     }
-    #[allow(dead_code)]
-    fn from_strings(dominant: &str, recessive: &str) -> Chromosome {
-        Chromosome {
-            dominant: dominant.parse::<Zygote>().unwrap(),
-            recessive: recessive.parse::<Zygote>().unwrap(),
-            decoded: vec![0;dominant.len()],
+    fn mark_dirty_genes(&mut self, begin: usize, amount: usize) {
+        let end = std::cmp::min(begin.saturating_add(amount), self.genes_amount());
+        if begin < end {
+            for p in begin / 64..=(end - 1) / 64 {
+                self.mark_dirty(p);
+            }
         }
     }
+    fn mark_all_dirty(&mut self) {
+        for p in 0..self.dirty.len() {
+            self.mark_dirty(p);
+        }
+    }
+    fn decode_dirty_words<F: FnMut(u64, u64, u64, u64) -> u64>(&mut self, mut express: F) {
+        std::mem::swap(&mut self.changed_words, &mut self.dirty_words);
+        self.dirty_words.clear();
+        self.previous_words.clear();
+        for &p in &self.changed_words {
+            self.dirty[p] = false;
+            self.previous_words.push(self.decoded[p]);
+            self.decoded[p] = express(
+                self.dominant.get_d_u64(p),
+                self.dominant.get_v_u64(p),
+                self.recessive.get_d_u64(p),
+                self.recessive.get_v_u64(p),
+            );
+        }
+        self.mask_decoded_tail();
+    }
     pub fn cross_zygotes(&mut self, begin: usize, amount: usize) {
+        self.mark_dirty_genes(begin, amount);
         self.dominant.cross_bidirectional(
             &mut self.recessive,
             begin,
             amount,
         );
     }
-    pub fn cross_chromosomes(&mut self, that: &Chromosome, begin: usize, amount: usize) {
+    pub fn inherit_gametes(
+        &mut self,
+        first_parent: &Chromosome,
+        first_gamete: &Gamete,
+        second_parent: &Chromosome,
+        second_gamete: &Gamete,
+    ) {
+        first_parent.form_gamete(&mut self.dominant, first_gamete);
+        second_parent.form_gamete(&mut self.recessive, second_gamete);
+        self.mark_all_dirty();
+    }
+    fn form_gamete(&self, target: &mut Zygote, gamete: &Gamete) {
+        let (base, other) = if gamete.from_recessive {
+            (&self.recessive, &self.dominant)
+        } else {
+            (&self.dominant, &self.recessive)
+        };
+        target.overwrite(base);
+        target.cross(other, gamete.begin, gamete.amount);
+    }
+    pub fn mutate(&mut self, kind: ZygoteKind, pos: usize, new_gen: &Gen) {
+        self.mark_dirty(pos / 64);
+        match kind {
+            ZygoteKind::Dominant => self.dominant.mutate(pos, new_gen),
+            ZygoteKind::Recessive => self.recessive.mutate(pos, new_gen),
+        }
+    }
+    pub fn flip_dominance(&mut self, pos: usize) {
+        self.mark_dirty(pos / 64);
+        self.dominant.flip_dominance(pos);
+    }
+    pub fn genes_amount(&self) -> usize {
+        self.dominant.genes_amount()
+    }
+    fn mask_decoded_tail(&mut self) {
+        let last = self.dominant.u64s_amount() - 1;
+        self.decoded[last] &= self.dominant.last_u64_mask();
+    }
+}
+
+impl Genotype for Chromosome {
+    fn overwrite(&mut self, source: &Chromosome) {
+        for p in 0..self.dominant.u64s_amount() {
+            if self.dominant.get_d_u64(p) != source.dominant.get_d_u64(p)
+                || self.dominant.get_v_u64(p) != source.dominant.get_v_u64(p)
+                || self.recessive.get_d_u64(p) != source.recessive.get_d_u64(p)
+                || self.recessive.get_v_u64(p) != source.recessive.get_v_u64(p)
+            {
+                self.mark_dirty(p);
+            }
+        }
+        self.dominant.overwrite(&source.dominant);
+        self.recessive.overwrite(&source.recessive);
+    }
+    fn overwrite_with_decoded(&mut self, source: &Chromosome) {
+        self.dominant.overwrite(&source.dominant);
+        self.recessive.overwrite(&source.recessive);
+        self.decoded.copy_from_slice(&source.decoded);
+        self.dirty.copy_from_slice(&source.dirty);
+        self.dirty_words.clone_from(&source.dirty_words);
+        self.changed_words.clone_from(&source.changed_words);
+        self.previous_words.clone_from(&source.previous_words);
+    }
+    fn decode_genotype(&mut self) {
+        self.decode_dirty_words(dominance::standard_expression);
+    }
+    fn decode_genotype_with<D: DominanceRule>(&mut self, rule: &D, rng: &mut GaRng) {
+        self.decode_dirty_words(|dd, dv, rd, rv| rule.express(dd, dv, rd, rv, rng));
+    }
+    fn get_decoded(&self) -> &[u64] {
+        &self.decoded
+    }
+    fn get_decoded_changes(&self) -> Option<DecodedChanges<'_>> {
+        Some(DecodedChanges {
+            words: &self.changed_words,
+            previous_words: &self.previous_words,
+        })
+    }
+    fn invalidate_decoded(&mut self) {
+        self.mark_all_dirty();
+    }
+}
+
+impl LinearGenotype for Chromosome {
+    fn u64s_amount(&self) -> usize {
+        self.dominant.u64s_amount()
+    }
+    fn cross_chromosomes(&mut self, that: &Chromosome, begin: usize, amount: usize) {
+        self.mark_dirty_genes(begin, amount);
         self.dominant.cross(&that.dominant, begin, amount);
         self.recessive.cross(&that.recessive, begin, amount);
     }
-    pub fn mutate(&mut self, pos: usize, new_gen: &Gen) {
-        self.dominant.mutate(pos, new_gen);
+    fn cross_chromosomes_uniform(&mut self, that: &Chromosome, masks: &[u64]) {
+        for (p, mask) in masks.iter().enumerate() {
+            if *mask != 0 {
+                self.mark_dirty(p);
+            }
+        }
+        self.dominant.cross_by_masks(&that.dominant, masks);
+        self.recessive.cross_by_masks(&that.recessive, masks);
+    }
+    fn cross_chromosomes_k_point(&mut self, that: &Chromosome, points: &[usize]) {
+        for segment in points.chunks(2) {
+            let end = if segment.len() == 2 { segment[1] } else { usize::MAX };
+            self.mark_dirty_genes(segment[0], end - segment[0]);
+        }
+        self.dominant.cross_k_point(&that.dominant, points);
+        self.recessive.cross_k_point(&that.recessive, points);
+    }
+    fn flip_value(&mut self, pos: usize) {
+        self.mark_dirty(pos / 64);
+        self.dominant.flip_value(pos);
+    }
+    fn invert(&mut self, begin: usize, end: usize) {
+        self.mark_dirty_genes(begin, end - begin);
+        self.dominant.invert(begin, end);
+    }
+    fn swap(&mut self, first: usize, second: usize) {
+        self.mark_dirty(first / 64);
+        self.mark_dirty(second / 64);
+        self.dominant.swap(first, second);
+    }
+    fn scramble(&mut self, begin: usize, order: &[usize]) {
+        self.mark_dirty_genes(begin, order.len());
+        self.dominant.scramble(begin, order);
     }
 }
 
@@ -126,6 +308,27 @@ mod decoding_first_zygote_with_recessive_genes {
     }
 }
 
+#[cfg(test)]
+mod decoding_with_dominance_rule {
+    use super::*;
+    use dominance::{StandardDominance, DominantOneWins};
+    use random_utils;
+
+    #[test]
+    fn standard_rule_must_match_default_decoding() {
+        let mut chr = Chromosome::from_strings("DDdd", "DdDd");
+        chr.decode_genotype_with(&StandardDominance, &mut random_utils::seeded_rng(0));
+        assert_eq!(chr.decoded, vec![0b1100u64,0,0,0]);
+    }
+
+    #[test]
+    fn must_express_by_given_rule() {
+        let mut chr = Chromosome::from_strings("DDdd", "DdDd");
+        chr.decode_genotype_with(&DominantOneWins, &mut random_utils::seeded_rng(0));
+        assert_eq!(chr.decoded, vec![0b1110u64,0,0,0]);
+    }
+}
+
 #[cfg(test)]
 mod crossing_zygote {
     use super::*;
@@ -174,19 +377,157 @@ mod crossing_zygote {
         )
     }
 
+    #[test]
+    fn cross_chromosomes_uniform() {
+        let mut first = Chromosome::from_strings(
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
+            "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr",
+        );
+        let second = Chromosome::from_strings(
+            "DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD",
+            "RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR",
+        );
+        first.cross_chromosomes_uniform(&second, &[0b1001_0110]);
+        assert_eq!(
+            first.to_string(),
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd DddD dDDd\
+            \nrrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr RrrR rRRr"
+        )
+    }
+
+    #[test]
+    fn cross_chromosomes_k_point() {
+        let mut first = Chromosome::from_strings(
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
+            "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr",
+        );
+        let second = Chromosome::from_strings(
+            "DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD",
+            "RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR",
+        );
+        first.cross_chromosomes_k_point(&second, &[1, 3, 6, 7]);
+        assert_eq!(
+            first.to_string(),
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dDdd dDDd\
+            \nrrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rRrr rRRr"
+        )
+    }
+
+    #[test]
+    fn inherit_gametes_must_take_one_recombined_zygote_from_each_parent() {
+        let mut child = Chromosome::from_strings(
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
+        );
+        let first = Chromosome::from_strings(
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
+            "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr",
+        );
+        let second = Chromosome::from_strings(
+            "DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD",
+            "RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR",
+        );
+        let first_gamete = Gamete {
+            from_recessive: false,
+            begin: 1,
+            amount: 2,
+        };
+        let second_gamete = Gamete {
+            from_recessive: true,
+            begin: 4,
+            amount: 3,
+        };
+        child.inherit_gametes(&first, &first_gamete, &second, &second_gamete);
+        assert_eq!(
+            child.to_string(),
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd drrd\
+            \nRRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RDDD RRRR"
+        );
+        child.inherit_gametes(&first, &first_gamete.complement(), &second, &second_gamete.complement());
+        assert_eq!(
+            child.to_string(),
+            "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rddr\
+            \nDDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DRRR DDDD"
+        );
+    }
+
     #[test]
     fn mutate_gen_in_dominant() {
         let mut chr = Chromosome::from_strings(
             "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
             "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr",
         );
-        chr.mutate(2, &Gen::R1);
+        chr.mutate(ZygoteKind::Dominant, 2, &Gen::R1);
         assert_eq!(
             chr.to_string(),
             "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dRdd\
             \nrrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr"
         )
     }
+
+    #[test]
+    fn mutate_gen_in_recessive() {
+        let mut chr = Chromosome::from_strings(
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
+            "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr",
+        );
+        chr.mutate(ZygoteKind::Recessive, 1, &Gen::D1);
+        assert_eq!(
+            chr.to_string(),
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd\
+            \nrrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrDr"
+        )
+    }
+}
+
+#[cfg(test)]
+mod incremental_decoding {
+    use super::*;
+
+    const WORDS: &str = "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd ";
+
+    fn chromosome(genes: &str) -> Chromosome {
+        let mut chr = Chromosome::new(
+            genes.repeat(3).parse::<Zygote>().unwrap(),
+            "rrrr".repeat(48).parse::<Zygote>().unwrap(),
+        );
+        chr.decode_genotype();
+        chr
+    }
+
+    #[test]
+    fn only_changed_words_must_be_decoded_again() {
+        let mut chr = chromosome(WORDS);
+        assert_eq!(chr.get_decoded_changes().unwrap().words, &[0, 1, 2]);
+        chr.mutate(ZygoteKind::Dominant, 70, &Gen::D1);
+        chr.decode_genotype();
+        let changes = chr.get_decoded_changes().unwrap();
+        assert_eq!(changes.words, &[1]);
+        assert_eq!(changes.previous_words, &[0]);
+        assert_eq!(chr.decoded, vec![0, 1 << 6, 0]);
+    }
+
+    #[test]
+    fn overwrite_must_mark_only_differing_words() {
+        let mut chr = chromosome(WORDS);
+        let mut source = chromosome(WORDS);
+        source.flip_value(130);
+        chr.overwrite(&source);
+        chr.cross_chromosomes(&source, 10, 20);
+        chr.decode_genotype();
+        assert_eq!(chr.get_decoded_changes().unwrap().words, &[2, 0]);
+        assert_eq!(chr.decoded, vec![0, 0, 1 << 2]);
+    }
+
+    #[test]
+    fn invalidated_chromosome_must_be_decoded_whole() {
+        let mut chr = chromosome(WORDS);
+        chr.decode_genotype();
+        assert!(chr.get_decoded_changes().unwrap().words.is_empty());
+        chr.invalidate_decoded();
+        chr.decode_genotype();
+        assert_eq!(chr.get_decoded_changes().unwrap().words, &[0, 1, 2]);
+    }
 }
 
 fn _bools_to_str(bools: &[bool]) -> String {
@@ -0,0 +1,209 @@
+use conv::*;
+use global_constants::U64_BITS_AMOUNT;
+use utils::{self, width_mask};
+
+/// Takes `width` bits starting from bit `from` of the decoded genotype, the bit at `from`
+/// becomes the lowest one.
+pub fn extract_bits(decoded: &[u64], from: usize, width: usize) -> u64 {
+    let p = from / U64_BITS_AMOUNT;
+    let shift = from % U64_BITS_AMOUNT;
+    let mut bits = decoded[p] >> shift;
+    if shift + width > U64_BITS_AMOUNT {
+        bits |= decoded[p + 1] << (U64_BITS_AMOUNT - shift);
+    }
+    bits & width_mask(width)
+}
+
+#[derive(Clone, Debug)]
+pub enum Encoding {
+    Binary,
+    Gray,
+    TwosComplement,
+    SignMagnitude,
+}
+
+impl Encoding {
+    /// Position of the decoded value among all values of the given width sorted ascending,
+    /// together with the position of the highest value.
+    pub fn rank(&self, bits: u64, width: usize) -> (u64, u64) {
+        let bits = bits & width_mask(width);
+        match *self {
+            Encoding::Binary => (bits, width_mask(width)),
+            Encoding::Gray => (utils::gray_to_bin(bits, width), width_mask(width)),
+            Encoding::TwosComplement => (bits ^ 1 << (width - 1), width_mask(width)),
+            Encoding::SignMagnitude => {
+                let zero = width_mask(width - 1);
+                let magnitude = bits & zero;
+                let rank = if bits >> (width - 1) == 1 {
+                    zero - magnitude
+                } else {
+                    zero + magnitude
+                };
+                (rank, width_mask(width) - 1)
+            }
+        }
+    }
+
+    /// Unsigned encodings must not be wider than 63 bits to fit into `i64`.
+    pub fn decode_integer(&self, bits: u64, width: usize) -> i64 {
+        let bits = bits & width_mask(width);
+        match *self {
+            Encoding::Binary => i64::value_from(bits).unwrap(),
+            Encoding::Gray => i64::value_from(utils::gray_to_bin(bits, width)).unwrap(),
+            Encoding::TwosComplement => utils::twos_complement_to_i64(bits, width),
+            Encoding::SignMagnitude => utils::sign_magnitude_to_i64(bits, width),
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        match *self {
+            Encoding::Binary | Encoding::Gray => false,
+            Encoding::TwosComplement | Encoding::SignMagnitude => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RealVariable {
+    pub width: usize,
+    pub min: f64,
+    pub max: f64,
+    pub encoding: Encoding,
+}
+
+impl RealVariable {
+    pub fn new(width: usize, min: f64, max: f64) -> Self {
+        assert!(
+            width > 0 && width <= U64_BITS_AMOUNT,
+            "variable width must be within [1, 64]"
+        );
+        assert!(min <= max, "variable min must not exceed max");
+        Self {
+            width,
+            min,
+            max,
+            encoding: Encoding::Binary,
+        }
+    }
+
+    pub fn map(&self, bits: u64) -> f64 {
+        let (rank, highest_rank) = self.encoding.rank(bits, self.width);
+        if highest_rank == 0 {
+            return self.min;
+        }
+        let steps = f64::approx_from(highest_rank).unwrap();
+        self.min + (self.max - self.min) * f64::approx_from(rank).unwrap() / steps
+    }
+}
+
+/// Splits the decoded genotype into consecutive variables, each one mapped linearly
+/// from its bits onto `[min, max]`.
+pub struct RealDecoder {
+    variables: Vec<RealVariable>,
+}
+
+impl RealDecoder {
+    pub fn new(variables: Vec<RealVariable>) -> Self {
+        Self { variables }
+    }
+
+    pub fn bits_amount(&self) -> usize {
+        self.variables.iter().map(|v| v.width).sum()
+    }
+
+    pub fn decode(&self, decoded: &[u64]) -> Vec<f64> {
+        let mut values = Vec::with_capacity(self.variables.len());
+        self.decode_into(decoded, &mut values);
+        values
+    }
+
+    pub fn decode_into(&self, decoded: &[u64], values: &mut Vec<f64>) {
+        values.clear();
+        let mut from = 0;
+        for variable in &self.variables {
+            values.push(variable.map(extract_bits(decoded, from, variable.width)));
+            from += variable.width;
+        }
+    }
+}
+
+#[cfg(test)]
+mod extracting_bits {
+    use super::*;
+
+    #[test]
+    fn must_take_bits_inside_word() {
+        assert_eq!(extract_bits(&[0b1011_0100], 2, 5), 0b01101);
+    }
+
+    #[test]
+    fn must_take_bits_across_words() {
+        assert_eq!(extract_bits(&[0xF000_0000_0000_0000, 0b101], 60, 7), 0b101_1111);
+    }
+
+    #[test]
+    fn must_take_whole_words() {
+        assert_eq!(extract_bits(&[1, !0], 64, 64), !0);
+        assert_eq!(extract_bits(&[0xFF00_0000_0000_0000, 0xFF], 56, 64), 0xFFFF);
+    }
+}
+
+#[cfg(test)]
+mod real_decoding {
+    use super::*;
+
+    #[test]
+    fn bounds_must_map_onto_interval_ends() {
+        let variable = RealVariable::new(10, -5.0, 5.0);
+        assert_eq!(variable.map(0), -5.0);
+        assert_eq!(variable.map(0b11_1111_1111), 5.0);
+        assert_eq!(RealVariable::new(64, 0.0, 1.0).map(!0), 1.0);
+    }
+
+    #[test]
+    fn decoder_must_split_bits_into_variables_of_given_widths() {
+        let decoder = RealDecoder::new(vec![
+            RealVariable::new(10, 0.0, 1023.0),
+            RealVariable::new(16, 0.0, 1.0),
+            RealVariable::new(23, -1.0, 1.0),
+            RealVariable::new(30, 0.0, 1.0),
+        ]);
+        assert_eq!(decoder.bits_amount(), 79);
+        let decoded = [7 | 0xFFFF << 10 | 1 << 26 | 0x7FFF << 49, 0x7FFF];
+        assert_eq!(decoder.decode(&decoded), vec![7.0, 1.0, -1.0 + 2.0 / 8_388_607.0, 1.0]);
+    }
+}
+
+#[cfg(test)]
+mod encodings {
+    use super::*;
+
+    fn ranks(encoding: Encoding, width: usize) -> Vec<u64> {
+        (0..1u64 << width).map(|bits| encoding.rank(bits, width).0).collect()
+    }
+
+    #[test]
+    fn ranks_must_follow_decoded_values() {
+        assert_eq!(ranks(Encoding::Binary, 2), vec![0, 1, 2, 3]);
+        assert_eq!(ranks(Encoding::Gray, 2), vec![0, 1, 3, 2]);
+        assert_eq!(ranks(Encoding::TwosComplement, 2), vec![2, 3, 0, 1]);
+        assert_eq!(ranks(Encoding::SignMagnitude, 2), vec![1, 2, 1, 0]);
+    }
+
+    #[test]
+    fn every_variable_must_use_its_own_encoding() {
+        let mut gray = RealVariable::new(2, 0.0, 3.0);
+        gray.encoding = Encoding::Gray;
+        let mut signed = RealVariable::new(2, -1.0, 1.0);
+        signed.encoding = Encoding::SignMagnitude;
+        let decoder = RealDecoder::new(vec![RealVariable::new(2, 0.0, 3.0), gray, signed]);
+        assert_eq!(decoder.decode(&[0b11_10_10]), vec![2.0, 3.0, -1.0]);
+    }
+
+    #[test]
+    fn single_sign_bit_must_map_to_min() {
+        let mut variable = RealVariable::new(1, -1.0, 1.0);
+        variable.encoding = Encoding::SignMagnitude;
+        assert_eq!(variable.map(1), -1.0);
+    }
+}
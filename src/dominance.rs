@@ -0,0 +1,117 @@
+use random_utils::GaRng;
+use rand::Rng;
+
+/// Expresses 64 loci at once from dominance and value words of the dominant (`dd`, `dv`)
+/// and recessive (`rd`, `rv`) zygotes.
+pub trait DominanceRule {
+    fn express(&self, dd: u64, dv: u64, rd: u64, rv: u64, rng: &mut GaRng) -> u64;
+}
+
+pub fn standard_expression(dd: u64, dv: u64, rd: u64, rv: u64) -> u64 {
+    dv & !rd | rd & rv & !dd | dd & dv
+}
+
+/// A dominant gene wins over a recessive one, on a tie the gene of the dominant zygote wins.
+#[derive(Clone, Default)]
+pub struct StandardDominance;
+
+impl DominanceRule for StandardDominance {
+    fn express(&self, dd: u64, dv: u64, rd: u64, rv: u64, _rng: &mut GaRng) -> u64 {
+        standard_expression(dd, dv, rd, rv)
+    }
+}
+
+/// A dominant gene wins over a recessive one, on a tie a random zygote wins.
+#[derive(Clone, Default)]
+pub struct RandomHeterozygousExpression;
+
+impl DominanceRule for RandomHeterozygousExpression {
+    fn express(&self, dd: u64, dv: u64, rd: u64, rv: u64, rng: &mut GaRng) -> u64 {
+        let tie = !(dd ^ rd);
+        let mask = rng.gen::<u64>();
+        !tie & (dd & dv | rd & rv) | tie & (dv & !mask | rv & mask)
+    }
+}
+
+/// Like the standard rule, but a dominant one is expressed whenever either zygote carries it.
+#[derive(Clone, Default)]
+pub struct DominantOneWins;
+
+impl DominanceRule for DominantOneWins {
+    fn express(&self, dd: u64, dv: u64, rd: u64, rv: u64, _rng: &mut GaRng) -> u64 {
+        standard_expression(dd, dv, rd, rv) | dd & dv | rd & rv
+    }
+}
+
+/// Hollstien-Holland triallelic scheme with alleles 0 (`D0`, `R0`), recessive 1 (`R1`) and
+/// dominant 1 (`D1`): dominant 1 wins over everything, 0 wins over recessive 1 and two
+/// recessive ones are expressed as 1.
+#[derive(Clone, Default)]
+pub struct HollstienHollandTriallelic;
+
+impl DominanceRule for HollstienHollandTriallelic {
+    fn express(&self, dd: u64, dv: u64, rd: u64, rv: u64, _rng: &mut GaRng) -> u64 {
+        dd & dv | rd & rv | dv & rv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use random_utils;
+
+    // dominance and value words for loci holding D1, D0, R1, R0 genes at positions 3, 2, 1, 0
+    const D: u64 = 0b1100;
+    const V: u64 = 0b1010;
+
+    fn express_all(rule: &dyn DominanceRule, rng: &mut GaRng) -> Vec<u64> {
+        (0..4)
+            .flat_map(|first| (0..4).map(move |second| (first, second)))
+            .map(|(first, second)| {
+                let bit = |w: u64, p: usize| (w >> p) & 1;
+                rule.express(bit(D, first), bit(V, first), bit(D, second), bit(V, second), rng) & 1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn standard_must_prefer_dominant_zygote_on_tie() {
+        let mut rng = random_utils::seeded_rng(0);
+        // rows: dominant zygote gene R0, R1, D0, D1; columns: recessive zygote gene in the same order
+        assert_eq!(
+            express_all(&StandardDominance, &mut rng),
+            vec![0, 0, 0, 1, 1, 1, 0, 1, 0, 0, 0, 0, 1, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn dominant_one_must_always_win() {
+        let mut rng = random_utils::seeded_rng(0);
+        assert_eq!(
+            express_all(&DominantOneWins, &mut rng),
+            vec![0, 0, 0, 1, 1, 1, 0, 1, 0, 0, 0, 1, 1, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn triallelic_must_express_two_recessive_ones_as_one() {
+        let mut rng = random_utils::seeded_rng(0);
+        assert_eq!(
+            express_all(&HollstienHollandTriallelic, &mut rng),
+            vec![0, 0, 0, 1, 0, 1, 0, 1, 0, 0, 0, 1, 1, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn random_expression_must_follow_dominance_and_mix_ties() {
+        let mut rng = random_utils::seeded_rng(0);
+        let rule = RandomHeterozygousExpression;
+        let mut expressed_ones = 0;
+        for _ in 0..1000 {
+            assert_eq!(rule.express(!0, 0, 0, !0, &mut rng), 0);
+            assert_eq!(rule.express(0, 0, !0, !0, &mut rng), !0);
+            expressed_ones += rule.express(!0, 0, !0, !0, &mut rng).count_ones();
+        }
+        assert!(expressed_ones > 30_000 && expressed_ones < 34_000);
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::{BTreeMap, HashMap};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use conv::*;
+
+struct CacheEntry {
+    decoded: Vec<u64>,
+    fitness: f64,
+    hash: u64,
+    last_used: u64,
+}
+
+/// Bounded least recently used cache of fitnesses keyed by the decoded genotype. Entries are
+/// looked up by hash and then compared by the whole decoded genotype, so collisions are safe.
+/// Only deterministic fitness calculators should be cached.
+pub struct FitnessCache {
+    capacity: usize,
+    entries: Vec<CacheEntry>,
+    by_hash: HashMap<u64, Vec<usize>>,
+    by_last_used: BTreeMap<u64, usize>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl FitnessCache {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "fitness cache capacity must be positive");
+        Self {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+            by_hash: HashMap::with_capacity(capacity),
+            by_last_used: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn get(&mut self, decoded: &[u64]) -> Option<f64> {
+        match self.find(hash_decoded(decoded), decoded) {
+            Some(pos) => {
+                self.hits += 1;
+                self.touch(pos);
+                Some(self.entries[pos].fitness)
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Evicts the least recently used entry when the cache is full, a known genotype only
+    /// gets its fitness replaced.
+    pub fn insert(&mut self, decoded: &[u64], fitness: f64) {
+        let hash = hash_decoded(decoded);
+        if let Some(pos) = self.find(hash, decoded) {
+            self.entries[pos].fitness = fitness;
+            self.touch(pos);
+            return;
+        }
+        let pos = if self.entries.len() < self.capacity {
+            self.entries.push(CacheEntry {
+                decoded: decoded.to_vec(),
+                fitness,
+                hash,
+                last_used: 0,
+            });
+            self.entries.len() - 1
+        } else {
+            let pos = self.evict_least_recently_used();
+            let entry = &mut self.entries[pos];
+            entry.decoded.clear();
+            entry.decoded.extend_from_slice(decoded);
+            entry.fitness = fitness;
+            entry.hash = hash;
+            pos
+        };
+        self.by_hash.entry(hash).or_default().push(pos);
+        self.touch(pos);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get_hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn get_misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            f64::approx_from(self.hits).unwrap() / f64::approx_from(lookups).unwrap()
+        }
+    }
+
+    fn find(&self, hash: u64, decoded: &[u64]) -> Option<usize> {
+        self.by_hash
+            .get(&hash)
+            .and_then(|positions| {
+                positions.iter().find(|p| self.entries[**p].decoded == decoded)
+            })
+            .cloned()
+    }
+
+    fn touch(&mut self, pos: usize) {
+        self.by_last_used.remove(&self.entries[pos].last_used);
+        self.tick += 1;
+        self.entries[pos].last_used = self.tick;
+        self.by_last_used.insert(self.tick, pos);
+    }
+
+    fn evict_least_recently_used(&mut self) -> usize {
+        let (&last_used, &pos) = self.by_last_used.iter().next().unwrap();
+        self.by_last_used.remove(&last_used);
+        let hash = self.entries[pos].hash;
+        let emptied = {
+            let positions = self.by_hash.get_mut(&hash).unwrap();
+            positions.retain(|p| *p != pos);
+            positions.is_empty()
+        };
+        if emptied {
+            self.by_hash.remove(&hash);
+        }
+        pos
+    }
+}
+
+fn hash_decoded(decoded: &[u64]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    decoded.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_and_misses_must_be_counted() {
+        let mut cache = FitnessCache::new(2);
+        assert_eq!(cache.get(&[1, 2]), None);
+        cache.insert(&[1, 2], 3.0);
+        assert_eq!(cache.get(&[1, 2]), Some(3.0));
+        assert_eq!(cache.get(&[2, 1]), None);
+        assert_eq!((cache.get_hits(), cache.get_misses()), (1, 2));
+        assert_eq!(cache.hit_ratio(), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn least_recently_used_entry_must_be_evicted() {
+        let mut cache = FitnessCache::new(2);
+        cache.insert(&[1], 1.0);
+        cache.insert(&[2], 2.0);
+        assert_eq!(cache.get(&[1]), Some(1.0));
+        cache.insert(&[3], 3.0);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&[2]), None);
+        assert_eq!(cache.get(&[1]), Some(1.0));
+        assert_eq!(cache.get(&[3]), Some(3.0));
+    }
+
+    #[test]
+    fn known_genotype_must_not_be_inserted_twice() {
+        let mut cache = FitnessCache::new(2);
+        cache.insert(&[1], 1.0);
+        cache.insert(&[1], 2.0);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&[1]), Some(2.0));
+    }
+}
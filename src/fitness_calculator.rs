@@ -1,5 +1,69 @@
+use decoding::RealDecoder;
+use genotype::DecodedChanges;
+use generation::Objective;
+
 pub trait FitnessCalculator {
-    fn calc_fitness(decoded_genotype: &[u64]) -> f64;
+    fn calc_fitness(&mut self, decoded_genotype: &[u64]) -> f64;
+
+    /// Optional hook for genotypes tracking decoded changes: `previous_fitness` was calculated
+    /// for the decoded genotype before `changes` were applied to it.
+    fn calc_fitness_incremental(
+        &mut self,
+        decoded_genotype: &[u64],
+        _changes: &DecodedChanges,
+        _previous_fitness: f64,
+    ) -> f64 {
+        self.calc_fitness(decoded_genotype)
+    }
 }
 
 pub struct FitnessCalculatorStruct;
+
+/// Calculates one fitness per objective, in the order of `get_objectives`.
+pub trait MultiObjectiveFitnessCalculator {
+    fn get_objectives(&self) -> Vec<Objective>;
+    fn calc_fitnesses(&mut self, decoded_genotype: &[u64]) -> Vec<f64>;
+}
+
+pub trait RealFitnessCalculator {
+    fn calc_fitness(&mut self, variables: &[f64]) -> f64;
+}
+
+/// Decodes the genotype into real variables before passing it to a `RealFitnessCalculator`.
+pub struct RealFitnessCalculatorAdapter<RFC: RealFitnessCalculator> {
+    decoder: RealDecoder,
+    real_fitness_calculator: RFC,
+    variables: Vec<f64>,
+}
+
+impl<RFC: RealFitnessCalculator> RealFitnessCalculatorAdapter<RFC> {
+    pub fn new(
+        decoder: RealDecoder,
+        real_fitness_calculator: RFC,
+        genes_amount: usize,
+    ) -> Result<Self, String> {
+        if decoder.bits_amount() > genes_amount {
+            return Err(format!(
+                "variables take {} bits but the chromosome has only {} genes",
+                decoder.bits_amount(),
+                genes_amount
+            ));
+        }
+        Ok(Self {
+            decoder,
+            real_fitness_calculator,
+            variables: Vec::new(),
+        })
+    }
+
+    pub fn get_decoder(&self) -> &RealDecoder {
+        &self.decoder
+    }
+}
+
+impl<RFC: RealFitnessCalculator> FitnessCalculator for RealFitnessCalculatorAdapter<RFC> {
+    fn calc_fitness(&mut self, decoded_genotype: &[u64]) -> f64 {
+        self.decoder.decode_into(decoded_genotype, &mut self.variables);
+        self.real_fitness_calculator.calc_fitness(&self.variables)
+    }
+}
@@ -0,0 +1,167 @@
+use generation::Objective;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SegmentLength {
+    UpToChromosomeLength,
+    Uniform { min: usize, max: usize },
+    Fixed(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Crossover {
+    Segment,
+    Uniform,
+    KPoint(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reproduction {
+    /// Each zygote of a child is the same zygote of the first parent crossed with the second one.
+    ZygoteWise,
+    /// Each parent recombines its own zygotes by segment crossing into a gamete,
+    /// a child gets one gamete from each parent. Only `Crossover::Segment` forms gametes.
+    Meiosis,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Conception {
+    SingleChild,
+    /// Both recombinants of a parent pair are kept, taking two slots of the next generation.
+    ComplementaryPair,
+}
+
+/// Every `_rate` is per gene: the gene amount times the rate is the expected amount of events
+/// of the kind per chromosome, and a child gets at most one of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaConfig {
+    pub population_size: usize,
+    pub chromosome_genes_amount: usize,
+    /// Gene counts of the chromosomes of a `Genome`, each one is bred with its own gene count.
+    /// Empty means a single chromosome of `chromosome_genes_amount` genes.
+    pub genome_genes_amounts: Vec<usize>,
+    /// Rate of mutations in the dominant zygote of a conceived child.
+    pub mutation_rate: f64,
+    /// Rate of mutations in the recessive zygote of a conceived child.
+    pub recessive_mutation_rate: f64,
+    /// Rate of genes of a child getting flipped dominance and keeping the value.
+    pub dominance_flip_rate: f64,
+    /// Rate of genes of a child getting flipped value and keeping the dominance.
+    pub value_flip_rate: f64,
+    /// Rate of reversing the order of genes of a child between two random loci.
+    pub inversion_rate: f64,
+    /// Rate of swapping two random loci of a child.
+    pub swap_rate: f64,
+    /// Rate of shuffling genes of a child between two random loci.
+    pub scramble_rate: f64,
+    /// Rate of exchanging a segment between dominant and recessive zygotes of a child.
+    pub zygotes_crossing_rate: f64,
+    /// Probability that a child is crossed with the second parent instead of cloning the first one.
+    pub chromosomes_crossing_probability: f64,
+    pub crossover: Crossover,
+    pub crossing_segment_length: SegmentLength,
+    pub reproduction: Reproduction,
+    pub conception: Conception,
+    /// Amount of the fittest individuals copied unchanged into the next generation.
+    pub elite_count: usize,
+    pub objective: Objective,
+    pub exclude_unviable_from_breeding: bool,
+}
+
+impl GaConfig {
+    pub fn new(population_size: usize, chromosome_genes_amount: usize) -> Self {
+        Self {
+            population_size,
+            chromosome_genes_amount,
+            genome_genes_amounts: Vec::new(),
+            mutation_rate: 1.0 / 10_000.0,
+            recessive_mutation_rate: 0.0,
+            dominance_flip_rate: 0.0,
+            value_flip_rate: 0.0,
+            inversion_rate: 0.0,
+            swap_rate: 0.0,
+            scramble_rate: 0.0,
+            zygotes_crossing_rate: 2.0 / 1_000_000.0,
+            chromosomes_crossing_probability: 1.0,
+            crossover: Crossover::Segment,
+            crossing_segment_length: SegmentLength::UpToChromosomeLength,
+            reproduction: Reproduction::ZygoteWise,
+            conception: Conception::SingleChild,
+            elite_count: 0,
+            objective: Objective::Maximize,
+            exclude_unviable_from_breeding: false,
+        }
+    }
+
+    /// Panics on a config no incubator can run with.
+    pub fn validate(&self) {
+        assert!(self.population_size > 0, "population size must be positive");
+        assert!(
+            self.elite_count <= self.population_size,
+            "elite count must not exceed population size"
+        );
+        assert!(
+            self.genome_genes_amounts().iter().all(|amount| *amount > 0),
+            "chromosome genes amount must be positive"
+        );
+        if let SegmentLength::Uniform { min, max } = self.crossing_segment_length {
+            assert!(
+                min <= max && max < usize::MAX,
+                "uniform segment length must have min <= max < usize::MAX"
+            );
+        }
+        if let Reproduction::Meiosis = self.reproduction {
+            assert!(
+                matches!(self.crossover, Crossover::Segment),
+                "meiosis supports segment crossover only"
+            );
+        }
+    }
+
+    /// Gene counts of the chromosomes of a `Genome`.
+    pub fn genome_genes_amounts(&self) -> &[usize] {
+        if self.genome_genes_amounts.is_empty() {
+            std::slice::from_ref(&self.chromosome_genes_amount)
+        } else {
+            &self.genome_genes_amounts
+        }
+    }
+}
+
+#[cfg(test)]
+mod validation {
+    use super::*;
+
+    #[test]
+    fn default_config_must_be_valid() {
+        GaConfig::new(10, 64).validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "population size must be positive")]
+    fn empty_population_must_be_rejected() {
+        GaConfig::new(0, 64).validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "chromosome genes amount must be positive")]
+    fn empty_chromosome_must_be_rejected() {
+        GaConfig::new(10, 0).validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "chromosome genes amount must be positive")]
+    fn empty_genome_chromosome_must_be_rejected() {
+        let mut config = GaConfig::new(10, 64);
+        config.genome_genes_amounts = vec![64, 0];
+        config.validate();
+    }
+
+    #[test]
+    #[should_panic(expected = "meiosis supports segment crossover only")]
+    fn meiosis_with_uniform_crossover_must_be_rejected() {
+        let mut config = GaConfig::new(10, 64);
+        config.reproduction = Reproduction::Meiosis;
+        config.crossover = Crossover::Uniform;
+        config.validate();
+    }
+}
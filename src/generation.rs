@@ -1,8 +1,41 @@
 use individual::Individual;
+use chromosome::Chromosome;
+use genotype::Genotype;
 use conv::*;
-use std::marker::PhantomData;
-use random_utils::ChoosingProbability;
+use random_utils::{ChoosingProbability, GaRng};
 use utils;
+use viability::{ViabilityPolicy, NoViabilityPolicy};
+use rand::Rng;
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Objective {
+    Maximize,
+    Minimize,
+}
+
+impl Objective {
+    pub fn orient(&self, fitness: f64) -> f64 {
+        match *self {
+            Objective::Maximize => fitness,
+            Objective::Minimize => -fitness,
+        }
+    }
+
+    pub fn is_better(&self, fitness: f64, than: f64) -> bool {
+        self.compare(fitness, than) == Ordering::Greater
+    }
+
+    /// NaN fitness is ordered as the worst one.
+    pub fn compare(&self, fitness: f64, than: f64) -> Ordering {
+        match (fitness.is_nan(), than.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => self.orient(fitness).total_cmp(&self.orient(than)),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Parents {
@@ -10,60 +43,501 @@ pub struct Parents {
     pub second_pos: usize,
 }
 
-pub struct Generation<CP: ChoosingProbability> {
-    pub individuals: Vec<Individual>,
-    pub min_fitness: f64,
-    pub max_fitness: f64,
-    phantom: PhantomData<CP>,
+pub trait Selection {
+    fn select_parent_pairs<G: Genotype>(
+        &self,
+        generation: &Generation<G>,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents>;
+}
+
+impl<CP: ChoosingProbability> Selection for CP {
+    fn select_parent_pairs<G: Genotype>(
+        &self,
+        generation: &Generation<G>,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents> {
+        generation.select_parent_pairs(self, pairs_amount, rng)
+    }
+}
+
+pub enum Replacement {
+    With,
+    Without,
+}
+
+pub struct TournamentSelection {
+    size: usize,
+    replacement: Replacement,
+}
+
+impl TournamentSelection {
+    pub fn new(size: usize, replacement: Replacement) -> Self {
+        assert!(size > 0, "tournament size must be positive");
+        Self { size, replacement }
+    }
+
+    fn run_tournament<G: Genotype>(
+        &self,
+        individuals: &[Individual<G>],
+        objective: &Objective,
+        candidates: &[usize],
+        pool: &mut Vec<usize>,
+        rng: &mut GaRng,
+    ) -> usize {
+        (0..self.size)
+            .map(|_| match self.replacement {
+                Replacement::With => candidates[rng.gen_range(0, candidates.len())],
+                Replacement::Without => {
+                    if pool.is_empty() {
+                        pool.extend_from_slice(candidates);
+                        rng.shuffle(pool);
+                    }
+                    pool.pop().unwrap()
+                }
+            })
+            .fold(None, |best: Option<usize>, pos| match best {
+                Some(b) if !objective.is_better(individuals[pos].fitness, individuals[b].fitness) => {
+                    Some(b)
+                }
+                _ => Some(pos),
+            })
+            .unwrap()
+    }
+}
+
+impl Selection for TournamentSelection {
+    fn select_parent_pairs<G: Genotype>(
+        &self,
+        generation: &Generation<G>,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents> {
+        let individuals = &generation.individuals;
+        let objective = &generation.objective;
+        let candidates = generation.find_breedable_positions();
+        let mut pool = Vec::with_capacity(candidates.len());
+        (0..pairs_amount)
+            .map(|_| {
+                let first_pos =
+                    self.run_tournament(individuals, objective, &candidates, &mut pool, rng);
+                let second_pos =
+                    self.run_tournament(individuals, objective, &candidates, &mut pool, rng);
+                Parents {
+                    first_pos,
+                    second_pos,
+                }
+            })
+            .collect()
+    }
+}
+
+pub enum FitnessScaling {
+    Normalized,
+    Offset(f64),
+}
+
+impl FitnessScaling {
+    fn weights<G: Genotype>(&self, generation: &Generation<G>) -> Vec<f64> {
+        generation
+            .individuals
+            .iter()
+            .map(|i| match *self {
+                FitnessScaling::Normalized => generation.normalize_fitness(i.fitness),
+                FitnessScaling::Offset(offset) => generation.objective.orient(i.fitness) + offset,
+            })
+            .map(sanitize_weight)
+            .collect()
+    }
+}
+
+/// Weights which are not finite or not positive can't be chosen.
+fn sanitize_weight(weight: f64) -> f64 {
+    if weight.is_finite() && weight > 0.0 {
+        weight
+    } else {
+        0.0
+    }
+}
+
+fn calc_cumulative_weights(weights: &[f64]) -> Vec<f64> {
+    weights
+        .iter()
+        .scan(0.0, |acc, w| {
+            *acc += w;
+            Some(*acc)
+        })
+        .collect()
+}
+
+fn find_cumulative_pos(cumulative_weights: &[f64], point: f64) -> usize {
+    let pos = match cumulative_weights.binary_search_by(|w| w.total_cmp(&point)) {
+        Ok(p) => p + 1,
+        Err(p) => p,
+    };
+    if pos < cumulative_weights.len() {
+        pos
+    } else {
+        cumulative_weights.len() - 1
+    }
+}
+
+fn spin_roulette(weights: &[f64], pairs_amount: usize, rng: &mut GaRng) -> Vec<Parents> {
+    let cumulative_weights = calc_cumulative_weights(weights);
+    let total = *cumulative_weights.last().unwrap();
+    let positions: Vec<usize> = (0..pairs_amount * 2)
+        .map(|_| {
+            find_cumulative_pos(&cumulative_weights, rng.gen::<f64>() * total)
+        })
+        .collect();
+    pair_up(&positions)
+}
+
+fn pair_up(positions: &[usize]) -> Vec<Parents> {
+    positions
+        .chunks(2)
+        .map(|pair| {
+            Parents {
+                first_pos: pair[0],
+                second_pos: pair[1],
+            }
+        })
+        .collect()
+}
+
+pub struct RouletteWheelSelection {
+    scaling: FitnessScaling,
+}
+
+impl RouletteWheelSelection {
+    pub fn new(scaling: FitnessScaling) -> Self {
+        Self { scaling }
+    }
+}
+
+impl Selection for RouletteWheelSelection {
+    fn select_parent_pairs<G: Genotype>(
+        &self,
+        generation: &Generation<G>,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents> {
+        let weights = generation.exclude_unbreedable(self.scaling.weights(generation));
+        spin_roulette(&weights, pairs_amount, rng)
+    }
+}
+
+pub struct StochasticUniversalSampling {
+    scaling: FitnessScaling,
+}
+
+impl StochasticUniversalSampling {
+    pub fn new(scaling: FitnessScaling) -> Self {
+        Self { scaling }
+    }
+}
+
+impl Selection for StochasticUniversalSampling {
+    fn select_parent_pairs<G: Genotype>(
+        &self,
+        generation: &Generation<G>,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents> {
+        let weights = generation.exclude_unbreedable(self.scaling.weights(generation));
+        let cumulative_weights = calc_cumulative_weights(&weights);
+        let total = *cumulative_weights.last().unwrap();
+        let pointers_amount = pairs_amount * 2;
+        let distance = total / f64::value_from(pointers_amount).unwrap();
+        let start = rng.gen::<f64>() * distance;
+        let mut positions: Vec<usize> = (0..pointers_amount)
+            .map(|p| {
+                let point = start + distance * f64::value_from(p).unwrap();
+                find_cumulative_pos(&cumulative_weights, point)
+            })
+            .collect();
+        rng.shuffle(&mut positions);
+        pair_up(&positions)
+    }
+}
+
+pub enum RankScheme {
+    Linear { pressure: f64 },
+    Exponential { base: f64 },
+}
+
+pub struct RankSelection {
+    scheme: RankScheme,
+}
+
+impl RankSelection {
+    pub fn new(scheme: RankScheme) -> Self {
+        match scheme {
+            RankScheme::Linear { pressure } => {
+                assert!(
+                    (1.0..=2.0).contains(&pressure),
+                    "linear rank pressure must be within [1, 2]"
+                )
+            }
+            RankScheme::Exponential { base } => {
+                assert!(
+                    base > 0.0 && base < 1.0,
+                    "exponential rank base must be within (0, 1)"
+                )
+            }
+        }
+        Self { scheme }
+    }
+
+    fn rank_weight(&self, rank: usize, len: usize) -> f64 {
+        match self.scheme {
+            RankScheme::Linear { pressure } => {
+                if len > 1 {
+                    2.0 - pressure +
+                        2.0 * (pressure - 1.0) * f64::value_from(rank).unwrap() /
+                            f64::value_from(len - 1).unwrap()
+                } else {
+                    1.0
+                }
+            }
+            RankScheme::Exponential { base } => {
+                base.powi(i32::value_from(len - 1 - rank).unwrap())
+            }
+        }
+    }
+}
+
+impl Selection for RankSelection {
+    fn select_parent_pairs<G: Genotype>(
+        &self,
+        generation: &Generation<G>,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents> {
+        let individuals = &generation.individuals;
+        let mut positions: Vec<usize> = (0..individuals.len()).collect();
+        positions.sort_by(|a, b| {
+            generation.objective.compare(
+                individuals[*a].fitness,
+                individuals[*b].fitness,
+            )
+        });
+        let mut weights = vec![0.0; individuals.len()];
+        for (rank, pos) in positions.iter().enumerate() {
+            weights[*pos] = self.rank_weight(rank, individuals.len());
+        }
+        spin_roulette(&generation.exclude_unbreedable(weights), pairs_amount, rng)
+    }
+}
+
+pub enum TemperatureSchedule {
+    Constant(f64),
+    Linear { initial: f64, step: f64, min: f64 },
+    Exponential { initial: f64, decay: f64, min: f64 },
+}
+
+impl TemperatureSchedule {
+    pub fn temperature(&self, generation_number: usize) -> f64 {
+        let n = f64::value_from(generation_number).unwrap();
+        match *self {
+            TemperatureSchedule::Constant(t) => t,
+            TemperatureSchedule::Linear { initial, step, min } => (initial - step * n).max(min),
+            TemperatureSchedule::Exponential { initial, decay, min } => {
+                (initial * decay.powf(n)).max(min)
+            }
+        }
+    }
+}
+
+pub struct BoltzmannSelection {
+    schedule: TemperatureSchedule,
+}
+
+impl BoltzmannSelection {
+    pub fn new(schedule: TemperatureSchedule) -> Self {
+        match schedule {
+            TemperatureSchedule::Constant(t) => {
+                assert!(t > 0.0, "constant temperature must be positive")
+            }
+            TemperatureSchedule::Linear { min, .. } |
+            TemperatureSchedule::Exponential { min, .. } => {
+                assert!(min > 0.0, "minimal temperature must be positive")
+            }
+        }
+        Self { schedule }
+    }
+}
+
+impl Selection for BoltzmannSelection {
+    fn select_parent_pairs<G: Genotype>(
+        &self,
+        generation: &Generation<G>,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents> {
+        let individuals = &generation.individuals;
+        let temperature = self.schedule.temperature(generation.number);
+        let objective = &generation.objective;
+        let first_fitness = objective.orient(individuals.first().unwrap().fitness);
+        let (min_fitness, max_fitness) =
+            individuals.iter().fold((first_fitness, first_fitness), |(min, max), i| {
+                let fitness = objective.orient(i.fitness);
+                (min.min(fitness), max.max(fitness))
+            });
+        let weights: Vec<f64> = individuals
+            .iter()
+            .map(|i| {
+                let normalized =
+                    utils::normalize_fitness(objective.orient(i.fitness), min_fitness, max_fitness);
+                ((normalized - 1.0) / temperature).exp()
+            })
+            .collect();
+        spin_roulette(&generation.exclude_unbreedable(weights), pairs_amount, rng)
+    }
 }
 
-impl<CP: ChoosingProbability> Generation<CP> {
-    pub fn new(individuals: Vec<Individual>) -> Self {
-        let overage_fitness = calc_overage_fitness(&individuals);
-        let min_fitness = find_worst_fitness(&individuals, overage_fitness);
-        let max_fitness = find_best_fitness(&individuals);
-        Self {
-            min_fitness,
-            max_fitness,
+pub struct Generation<G: Genotype = Chromosome> {
+    pub individuals: Vec<Individual<G>>,
+    pub worst_fitness: f64,
+    pub best_fitness: f64,
+    pub objective: Objective,
+    pub number: usize,
+    pub breedable: Vec<bool>,
+}
+
+impl<G: Genotype> Generation<G> {
+    pub fn new(individuals: Vec<Individual<G>>, objective: Objective) -> Self {
+        let len = individuals.len();
+        let mut generation = Self {
+            worst_fitness: 0.0,
+            best_fitness: 0.0,
             individuals,
-            phantom: PhantomData,
+            objective,
+            number: 0,
+            breedable: vec![true; len],
+        };
+        generation.update_fitness_bounds(&NoViabilityPolicy, false);
+        generation
+    }
+
+    pub fn update_fitness_bounds(
+        &mut self,
+        viability_policy: &dyn ViabilityPolicy,
+        exclude_unviable: bool,
+    ) {
+        let fitnesses = collect_fitnesses(&self.individuals);
+        let viable = viability_policy.find_viable(&fitnesses, &self.objective);
+        self.worst_fitness =
+            self.individuals[find_worst_viable_pos(&self.individuals, &viable, &self.objective)]
+                .fitness;
+        self.best_fitness = find_best_fitness(&self.individuals, &self.objective);
+        if exclude_unviable {
+            self.breedable = viable;
+        } else {
+            for b in &mut self.breedable {
+                *b = true;
+            }
+        }
+    }
+
+    /// Everybody is breedable when nobody is, so there are always parents to choose from.
+    fn find_breedable(&self) -> Vec<bool> {
+        if self.breedable.iter().any(|b| *b) {
+            self.breedable.clone()
+        } else {
+            vec![true; self.individuals.len()]
         }
     }
 
-    pub fn select_parent_pairs(&self) -> Vec<Parents> {
+    pub fn find_breedable_positions(&self) -> Vec<usize> {
+        let breedable = self.find_breedable();
+        (0..self.individuals.len())
+            .filter(|pos| breedable[*pos])
+            .collect()
+    }
+
+    fn exclude_unbreedable(&self, weights: Vec<f64>) -> Vec<f64> {
+        let breedable_weights: Vec<f64> = weights
+            .iter()
+            .zip(&self.breedable)
+            .map(|(w, b)| if *b { sanitize_weight(*w) } else { 0.0 })
+            .collect();
+        if breedable_weights.iter().any(|w| *w > 0.0) {
+            breedable_weights
+        } else if self.breedable.iter().any(|b| *b) {
+            self.breedable
+                .iter()
+                .map(|b| if *b { 1.0 } else { 0.0 })
+                .collect()
+        } else {
+            vec![1.0; weights.len()]
+        }
+    }
+
+    pub fn normalize_fitness(&self, fitness: f64) -> f64 {
+        utils::normalize_fitness(
+            self.objective.orient(fitness),
+            self.objective.orient(self.worst_fitness),
+            self.objective.orient(self.best_fitness),
+        )
+    }
+
+    pub fn select_parent_pairs<CP: ChoosingProbability>(
+        &self,
+        choosing_probability: &CP,
+        pairs_amount: usize,
+        rng: &mut GaRng,
+    ) -> Vec<Parents> {
+        let breedable = self.find_breedable();
         let mut parents = Vec::new();
         let mut pos = 0;
-        while pos < self.individuals.len() {
-            let first_pos = self.find_parent_pos(&mut pos);
-            let mut second_pos = self.find_parent_pos(&mut pos);
+        while parents.len() < pairs_amount {
+            let first_pos = self.find_parent_pos(choosing_probability, &breedable, &mut pos, rng);
+            let mut second_pos =
+                self.find_parent_pos(choosing_probability, &breedable, &mut pos, rng);
             if first_pos == second_pos {
-                second_pos = if pos > 0 {
+                let previous_pos = if pos > 0 {
                     pos - 1
                 } else {
                     self.individuals.len() - 1
+                };
+                if breedable[previous_pos] {
+                    second_pos = previous_pos;
                 }
             }
             parents.push(Parents {
                 first_pos,
                 second_pos,
             });
-            pos += 1;
+            pos = if pos + 1 < self.individuals.len() {
+                pos + 1
+            } else {
+                0
+            };
         }
         parents
     }
 
-    fn find_parent_pos(&self, pos: &mut usize) -> usize {
+    fn find_parent_pos<CP: ChoosingProbability>(
+        &self,
+        choosing_probability: &CP,
+        breedable: &[bool],
+        pos: &mut usize,
+        rng: &mut GaRng,
+    ) -> usize {
         loop {
             let candidate = &self.individuals[*pos];
-            if CP::select_individual_probability(utils::normalize_fitness(
-                candidate.fitness,
-                self.min_fitness,
-                self.max_fitness,
-            ))
+            if breedable[*pos] &&
+                choosing_probability.select_individual_probability(
+                    self.normalize_fitness(candidate.fitness),
+                    rng,
+                )
             {
                 return *pos;
             }
-            if *pos < self.individuals.len() {
+            if *pos + 1 < self.individuals.len() {
                 *pos += 1;
             } else {
                 *pos = 0;
@@ -73,16 +547,38 @@ impl<CP: ChoosingProbability> Generation<CP> {
 }
 
 
-pub fn calc_overage_fitness(individuals: &[Individual]) -> f64 {
+fn collect_fitnesses<G: Genotype>(individuals: &[Individual<G>]) -> Vec<f64> {
+    individuals.iter().map(|i| i.fitness).collect()
+}
+
+pub fn calc_overage_fitness<G: Genotype>(individuals: &[Individual<G>]) -> f64 {
     let fitnesses_sum: f64 = individuals.iter().map(|i| i.fitness).sum();
     fitnesses_sum / f64::value_from(individuals.len()).unwrap()
 }
 
-pub fn find_best_individual(individuals: &[Individual]) -> Individual {
+pub fn find_elite_positions<G: Genotype>(
+    individuals: &[Individual<G>],
+    amount: usize,
+    objective: &Objective,
+) -> Vec<usize> {
+    let mut positions: Vec<usize> = (0..individuals.len()).collect();
+    positions.sort_by(|a, b| {
+        objective.compare(individuals[*b].fitness, individuals[*a].fitness)
+    });
+    positions.truncate(amount);
+    positions
+}
+
+pub fn find_best_individual<G: Genotype>(
+    individuals: &[Individual<G>],
+    objective: &Objective,
+) -> Individual<G> {
     individuals
         .iter()
-        .fold(individuals.first().unwrap(), |acc, i| if acc.fitness >
-            i.fitness
+        .fold(individuals.first().unwrap(), |acc, i| if objective.is_better(
+            i.fitness,
+            acc.fitness,
+        )
         {
             i
         } else {
@@ -91,24 +587,22 @@ pub fn find_best_individual(individuals: &[Individual]) -> Individual {
         .clone()
 }
 
-pub fn find_worst_individual(individuals: &[Individual], overage_fitness: f64) -> Individual {
-    individuals
-        .iter()
-        .fold(individuals.first().unwrap(), |acc, i| {
-            if acc.fitness > i.fitness && filter_out_unviable_fetus(i, overage_fitness) {
-                i
-            } else {
-                acc
-            }
-        })
-        .clone()
+pub fn find_worst_individual<G: Genotype>(
+    individuals: &[Individual<G>],
+    objective: &Objective,
+    viability_policy: &dyn ViabilityPolicy,
+) -> Individual<G> {
+    let viable = viability_policy.find_viable(&collect_fitnesses(individuals), objective);
+    individuals[find_worst_viable_pos(individuals, &viable, objective)].clone()
 }
 
-pub fn find_best_fitness(individuals: &[Individual]) -> f64 {
+pub fn find_best_fitness<G: Genotype>(individuals: &[Individual<G>], objective: &Objective) -> f64 {
     individuals
         .iter()
-        .fold(individuals.first().unwrap(), |acc, i| if acc.fitness >
-            i.fitness
+        .fold(individuals.first().unwrap(), |acc, i| if objective.is_better(
+            i.fitness,
+            acc.fitness,
+        )
         {
             i
         } else {
@@ -117,24 +611,335 @@ pub fn find_best_fitness(individuals: &[Individual]) -> f64 {
         .fitness
 }
 
-fn filter_out_unviable_fetus(i: &Individual, overage_fitness: f64) -> bool {
-    let fitness = if i.fitness == 0.0 {
-        0.0
-    } else {
-        overage_fitness / i.fitness
-    };
-    fitness < 20.0
+pub fn find_worst_fitness<G: Genotype>(
+    individuals: &[Individual<G>],
+    objective: &Objective,
+    viability_policy: &dyn ViabilityPolicy,
+) -> f64 {
+    let viable = viability_policy.find_viable(&collect_fitnesses(individuals), objective);
+    individuals[find_worst_viable_pos(individuals, &viable, objective)].fitness
 }
 
-pub fn find_worst_fitness(individuals: &[Individual], overage_fitness: f64) -> f64 {
+fn find_worst_viable_pos<G: Genotype>(
+    individuals: &[Individual<G>],
+    viable: &[bool],
+    objective: &Objective,
+) -> usize {
     individuals
         .iter()
-        .fold(individuals.first().unwrap(), |acc, i| {
-            if acc.fitness > i.fitness && filter_out_unviable_fetus(i, overage_fitness) {
-                i
-            } else {
-                acc
-            }
+        .enumerate()
+        .filter(|&(pos, _)| viable[pos])
+        .fold(None, |worst: Option<usize>, (pos, i)| match worst {
+            Some(w) if !objective.is_better(individuals[w].fitness, i.fitness) => Some(w),
+            _ => Some(pos),
         })
-        .fitness
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+fn generation(fitnesses: &[f64]) -> Generation {
+    generation_with_objective(fitnesses, Objective::Maximize)
+}
+
+#[cfg(test)]
+fn generation_with_objective(fitnesses: &[f64], objective: Objective) -> Generation {
+    use zygote::Zygote;
+
+    Generation::new(
+        fitnesses
+            .iter()
+            .map(|f| {
+                Individual {
+                    chromosome: Chromosome::new(
+                        "d".parse::<Zygote>().unwrap(),
+                        "r".parse::<Zygote>().unwrap(),
+                    ),
+                    fitness: *f,
+                }
+            })
+            .collect(),
+        objective,
+    )
+}
+
+#[cfg(test)]
+mod tournament_selection {
+    use super::*;
+    use random_utils;
+
+    #[test]
+    fn must_return_requested_amount_of_pairs() {
+        let gen = generation(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = TournamentSelection::new(2, Replacement::With);
+        let parents = selection.select_parent_pairs(&gen, 7, &mut rng);
+        assert_eq!(parents.len(), 7);
+        assert!(parents.iter().all(|p| p.first_pos < 5 && p.second_pos < 5));
+    }
+
+    #[test]
+    fn tournament_of_whole_population_without_replacement_must_choose_the_best() {
+        let gen = generation(&[1.0, 5.0, 3.0, 4.0, 2.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = TournamentSelection::new(5, Replacement::Without);
+        let parents = selection.select_parent_pairs(&gen, 5, &mut rng);
+        assert!(parents.iter().all(|p| p.first_pos == 1 && p.second_pos == 1));
+    }
+
+    #[test]
+    fn tournament_without_replacement_must_give_each_individual_a_chance() {
+        let gen = generation(&[1.0, 2.0, 3.0, 4.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = TournamentSelection::new(1, Replacement::Without);
+        let parents = selection.select_parent_pairs(&gen, 2, &mut rng);
+        let mut selected: Vec<usize> = parents
+            .iter()
+            .flat_map(|p| vec![p.first_pos, p.second_pos])
+            .collect();
+        selected.sort();
+        assert_eq!(selected, vec![0, 1, 2, 3]);
+    }
+}
+
+#[cfg(test)]
+mod fitness_proportionate_selection {
+    use super::*;
+    use random_utils;
+
+    fn count_selections(parents: &[Parents], len: usize) -> Vec<usize> {
+        parents.iter().fold(vec![0; len], |mut acc, p| {
+            acc[p.first_pos] += 1;
+            acc[p.second_pos] += 1;
+            acc
+        })
+    }
+
+    #[test]
+    fn cumulative_pos_must_skip_zero_weights() {
+        let cumulative_weights = calc_cumulative_weights(&[0.0, 1.0, 0.0, 3.0]);
+        assert_eq!(cumulative_weights, vec![0.0, 1.0, 1.0, 4.0]);
+        assert_eq!(find_cumulative_pos(&cumulative_weights, 0.0), 1);
+        assert_eq!(find_cumulative_pos(&cumulative_weights, 0.5), 1);
+        assert_eq!(find_cumulative_pos(&cumulative_weights, 1.0), 3);
+        assert_eq!(find_cumulative_pos(&cumulative_weights, 3.9), 3);
+    }
+
+    #[test]
+    fn roulette_must_never_choose_individuals_with_zero_weight() {
+        let gen = generation(&[0.0, 10.0, 0.0, 30.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = RouletteWheelSelection::new(FitnessScaling::Offset(0.0));
+        let counts = count_selections(&selection.select_parent_pairs(&gen, 500, &mut rng), 4);
+        assert_eq!(counts[0], 0);
+        assert_eq!(counts[2], 0);
+        assert!(counts[3] > counts[1] * 2);
+    }
+
+    #[test]
+    fn roulette_must_never_choose_individuals_with_non_finite_fitness() {
+        let gen = generation(&[f64::NAN, 10.0, f64::INFINITY, 30.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = RouletteWheelSelection::new(FitnessScaling::Offset(0.0));
+        let counts = count_selections(&selection.select_parent_pairs(&gen, 100, &mut rng), 4);
+        assert_eq!((counts[0], counts[2]), (0, 0));
+    }
+
+    #[test]
+    fn roulette_must_not_favour_low_positions() {
+        let gen = generation(&[1.0, 1.0, 1.0, 1.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = RouletteWheelSelection::new(FitnessScaling::Normalized);
+        let counts = count_selections(&selection.select_parent_pairs(&gen, 2000, &mut rng), 4);
+        assert!(counts.iter().all(|c| *c > 900 && *c < 1100));
+    }
+
+    #[test]
+    fn sus_must_choose_individuals_proportionally_to_fitness() {
+        let gen = generation(&[-10.0, 0.0, 10.0, 20.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = StochasticUniversalSampling::new(FitnessScaling::Normalized);
+        let counts = count_selections(&selection.select_parent_pairs(&gen, 3, &mut rng), 4);
+        assert_eq!(counts, vec![0, 1, 2, 3]);
+    }
+}
+
+#[cfg(test)]
+mod rank_and_boltzmann_selection {
+    use super::*;
+    use random_utils;
+
+    fn count_selections(parents: &[Parents], len: usize) -> Vec<usize> {
+        parents.iter().fold(vec![0; len], |mut acc, p| {
+            acc[p.first_pos] += 1;
+            acc[p.second_pos] += 1;
+            acc
+        })
+    }
+
+    #[test]
+    fn linear_rank_weights_must_depend_only_on_rank() {
+        let selection = RankSelection::new(RankScheme::Linear { pressure: 2.0 });
+        assert_eq!(selection.rank_weight(0, 3), 0.0);
+        assert_eq!(selection.rank_weight(1, 3), 1.0);
+        assert_eq!(selection.rank_weight(2, 3), 2.0);
+    }
+
+    #[test]
+    fn exponential_rank_weights_must_decrease_by_base() {
+        let selection = RankSelection::new(RankScheme::Exponential { base: 0.5 });
+        assert_eq!(selection.rank_weight(2, 3), 1.0);
+        assert_eq!(selection.rank_weight(1, 3), 0.5);
+        assert_eq!(selection.rank_weight(0, 3), 0.25);
+    }
+
+    #[test]
+    fn rank_selection_must_not_collapse_on_fitness_of_different_magnitudes() {
+        let gen = generation(&[1.0, 1e3, 1e12, 1e6]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = RankSelection::new(RankScheme::Linear { pressure: 1.5 });
+        let counts = count_selections(&selection.select_parent_pairs(&gen, 2000, &mut rng), 4);
+        assert!(counts[0] < counts[1] && counts[1] < counts[3] && counts[3] < counts[2]);
+        assert!(counts[1] > 0);
+    }
+
+    #[test]
+    fn temperature_must_follow_schedule() {
+        let linear = TemperatureSchedule::Linear {
+            initial: 10.0,
+            step: 3.0,
+            min: 0.5,
+        };
+        assert_eq!(linear.temperature(0), 10.0);
+        assert_eq!(linear.temperature(3), 1.0);
+        assert_eq!(linear.temperature(4), 0.5);
+        let exponential = TemperatureSchedule::Exponential {
+            initial: 8.0,
+            decay: 0.5,
+            min: 1.5,
+        };
+        assert_eq!(exponential.temperature(2), 2.0);
+        assert_eq!(exponential.temperature(3), 1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "minimal temperature must be positive")]
+    fn temperature_must_not_cool_down_to_zero() {
+        BoltzmannSelection::new(TemperatureSchedule::Linear {
+            initial: 10.0,
+            step: 1.0,
+            min: 0.0,
+        });
+    }
+
+    #[test]
+    fn cooling_must_increase_selection_pressure() {
+        let mut gen = generation(&[1.0, 2.0, 3.0, 4.0]);
+        let selection = BoltzmannSelection::new(TemperatureSchedule::Exponential {
+            initial: 100.0,
+            decay: 0.1,
+            min: 0.01,
+        });
+        let mut rng = random_utils::seeded_rng(1);
+        let hot = count_selections(&selection.select_parent_pairs(&gen, 1000, &mut rng), 4);
+        gen.number = 4;
+        let cold = count_selections(&selection.select_parent_pairs(&gen, 1000, &mut rng), 4);
+        assert!(hot[0] > 400);
+        assert!(cold[3] > 1990);
+    }
+}
+
+#[cfg(test)]
+mod objective {
+    use super::*;
+    use random_utils;
+
+    #[test]
+    fn best_and_worst_fitness_must_follow_objective() {
+        let max_gen = generation_with_objective(&[3.0, 1.0, 2.0], Objective::Maximize);
+        assert_eq!((max_gen.best_fitness, max_gen.worst_fitness), (3.0, 1.0));
+        let min_gen = generation_with_objective(&[3.0, 1.0, 2.0], Objective::Minimize);
+        assert_eq!((min_gen.best_fitness, min_gen.worst_fitness), (1.0, 3.0));
+    }
+
+    #[test]
+    fn normalized_fitness_of_best_must_be_one() {
+        let gen = generation_with_objective(&[-10.0, 10.0, 0.0], Objective::Minimize);
+        assert_eq!(gen.normalize_fitness(-10.0), 1.0);
+        assert_eq!(gen.normalize_fitness(0.0), 0.5);
+        assert_eq!(gen.normalize_fitness(10.0), 0.0);
+    }
+
+    #[test]
+    fn elite_must_be_the_lowest_when_minimizing() {
+        let gen = generation_with_objective(&[3.0, 1.0, 4.0, 2.0], Objective::Minimize);
+        assert_eq!(
+            find_elite_positions(&gen.individuals, 2, &Objective::Minimize),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn nan_fitness_must_be_the_worst() {
+        for objective in &[Objective::Maximize, Objective::Minimize] {
+            let gen = generation_with_objective(&[f64::NAN, 1.0, 2.0], objective.clone());
+            assert_eq!(find_elite_positions(&gen.individuals, 2, objective).len(), 2);
+            assert!(!find_elite_positions(&gen.individuals, 2, objective).contains(&0));
+            assert!(objective.is_better(1.0, f64::NAN));
+        }
+        let gen = generation(&[f64::NAN, 1.0, 2.0]);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = RankSelection::new(RankScheme::Linear { pressure: 2.0 });
+        let parents = selection.select_parent_pairs(&gen, 10, &mut rng);
+        assert!(parents.iter().all(|p| p.first_pos != 0 && p.second_pos != 0));
+    }
+
+    #[test]
+    fn tournament_must_choose_the_lowest_when_minimizing() {
+        let gen = generation_with_objective(&[3.0, 1.0, 4.0, 2.0], Objective::Minimize);
+        let mut rng = random_utils::seeded_rng(1);
+        let selection = TournamentSelection::new(4, Replacement::Without);
+        let parents = selection.select_parent_pairs(&gen, 4, &mut rng);
+        assert!(parents.iter().all(|p| p.first_pos == 1 && p.second_pos == 1));
+    }
+}
+
+#[cfg(test)]
+mod viability {
+    use super::*;
+    use random_utils;
+    use random_utils::RandomChoosingProbability;
+    use viability::PercentileClippingPolicy;
+
+    #[test]
+    fn worst_fitness_must_ignore_unviable_individuals() {
+        let mut gen = generation(&[5.0, 1.0, 4.0, 2.0, 3.0]);
+        gen.update_fitness_bounds(&PercentileClippingPolicy::new(50.0), false);
+        assert_eq!(gen.worst_fitness, 3.0);
+        assert!(gen.breedable.iter().all(|b| *b));
+    }
+
+    #[test]
+    fn unviable_individuals_must_be_excluded_from_breeding_on_demand() {
+        let mut gen = generation(&[5.0, 1.0, 4.0, 2.0, 3.0]);
+        gen.update_fitness_bounds(&PercentileClippingPolicy::new(50.0), true);
+        let mut rng = random_utils::seeded_rng(1);
+        let tournament = TournamentSelection::new(1, Replacement::With);
+        let roulette = RouletteWheelSelection::new(FitnessScaling::Offset(0.0));
+        let parents: Vec<Parents> = tournament
+            .select_parent_pairs(&gen, 50, &mut rng)
+            .into_iter()
+            .chain(roulette.select_parent_pairs(&gen, 50, &mut rng))
+            .collect();
+        assert!(parents.iter().all(|p| {
+            p.first_pos != 1 && p.first_pos != 3 && p.second_pos != 1 && p.second_pos != 3
+        }));
+    }
+    #[test]
+    fn everybody_must_be_breedable_when_nobody_is() {
+        let mut gen = generation(&[5.0, 1.0, 4.0]);
+        gen.breedable = vec![false; 3];
+        let mut rng = random_utils::seeded_rng(1);
+        let parents = RandomChoosingProbability.select_parent_pairs(&gen, 3, &mut rng);
+        assert_eq!(parents.len(), 3);
+    }
 }
@@ -0,0 +1,111 @@
+use chromosome::Chromosome;
+use genotype::Genotype;
+use dominance::DominanceRule;
+use random_utils::GaRng;
+use std::fmt;
+
+/// A set of chromosomes with independent lengths. Each chromosome is decoded into its own
+/// buffer and `get_decoded` gives all of them concatenated in chromosome order.
+#[derive(Clone)]
+pub struct Genome {
+    pub chromosomes: Vec<Chromosome>,
+    decoded: Vec<u64>,
+}
+
+impl fmt::Display for Genome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chromosomes: Vec<String> = self.chromosomes.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", chromosomes.join("\n"))
+    }
+}
+
+impl fmt::Debug for Genome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self, f)
+    }
+}
+
+impl Genome {
+    pub fn new(chromosomes: Vec<Chromosome>) -> Self {
+        let u64s_amount = chromosomes.iter().map(|c| c.decoded.len()).sum();
+        Self {
+            chromosomes,
+            decoded: vec![0; u64s_amount],
+        }
+    }
+
+    pub fn get_chromosome_decoded(&self, pos: usize) -> &[u64] {
+        &self.chromosomes[pos].decoded
+    }
+
+    fn concat_decoded(&mut self) {
+        let mut p = 0;
+        for chromosome in &self.chromosomes {
+            let len = chromosome.decoded.len();
+            self.decoded[p..p + len].copy_from_slice(&chromosome.decoded);
+            p += len;
+        }
+    }
+}
+
+impl Genotype for Genome {
+    fn overwrite(&mut self, source: &Genome) {
+        for (chromosome, source) in self.chromosomes.iter_mut().zip(&source.chromosomes) {
+            chromosome.overwrite(source);
+        }
+    }
+    fn overwrite_with_decoded(&mut self, source: &Genome) {
+        for (chromosome, source) in self.chromosomes.iter_mut().zip(&source.chromosomes) {
+            chromosome.overwrite_with_decoded(source);
+        }
+        self.decoded.copy_from_slice(&source.decoded);
+    }
+    fn decode_genotype(&mut self) {
+        for chromosome in &mut self.chromosomes {
+            chromosome.decode_genotype();
+        }
+        self.concat_decoded();
+    }
+    fn decode_genotype_with<D: DominanceRule>(&mut self, rule: &D, rng: &mut GaRng) {
+        for chromosome in &mut self.chromosomes {
+            chromosome.decode_genotype_with(rule, rng);
+        }
+        self.concat_decoded();
+    }
+    fn get_decoded(&self) -> &[u64] {
+        &self.decoded
+    }
+    fn invalidate_decoded(&mut self) {
+        for chromosome in &mut self.chromosomes {
+            chromosome.invalidate_decoded();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zygote::Zygote;
+
+    fn chromosome(dominant: &str, recessive: &str) -> Chromosome {
+        Chromosome::new(
+            dominant.parse::<Zygote>().unwrap(),
+            recessive.parse::<Zygote>().unwrap(),
+        )
+    }
+
+    #[test]
+    fn decoding_must_give_buffer_per_chromosome_and_concatenated_one() {
+        let mut genome = Genome::new(vec![
+            chromosome("DDdd", "rrrr"),
+            chromosome(
+                "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr dD",
+                "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rr",
+            ),
+        ]);
+        genome.decode_genotype();
+        assert_eq!(genome.get_chromosome_decoded(0), &[0b1100]);
+        assert_eq!(genome.get_chromosome_decoded(1), &[0b01, 0]);
+        assert_eq!(genome.get_decoded(), &[0b1100, 0b01, 0]);
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+use dominance::DominanceRule;
+use random_utils::GaRng;
+
+/// Indices of decoded words recalculated by the last decoding with the values they had before.
+pub struct DecodedChanges<'a> {
+    pub words: &'a [usize],
+    pub previous_words: &'a [u64],
+}
+
+pub trait Genotype: Clone + fmt::Display {
+    fn overwrite(&mut self, source: &Self);
+
+    fn overwrite_with_decoded(&mut self, source: &Self);
+
+    fn decode_genotype(&mut self);
+
+    fn decode_genotype_with<D: DominanceRule>(&mut self, rule: &D, rng: &mut GaRng);
+
+    fn get_decoded(&self) -> &[u64];
+
+    /// `None` when the genotype does not track which words the decoding changed.
+    fn get_decoded_changes(&self) -> Option<DecodedChanges<'_>> {
+        None
+    }
+
+    /// Makes the next decoding recalculate every word, e.g. after the dominance rule changed.
+    fn invalidate_decoded(&mut self) {}
+}
+
+/// A genotype made of a single line of loci, so it can be crossed and rearranged by positions.
+pub trait LinearGenotype: Genotype {
+    fn u64s_amount(&self) -> usize;
+
+    fn cross_chromosomes(&mut self, that: &Self, begin: usize, amount: usize);
+
+    fn cross_chromosomes_uniform(&mut self, that: &Self, masks: &[u64]);
+
+    fn cross_chromosomes_k_point(&mut self, that: &Self, points: &[usize]);
+
+    fn flip_value(&mut self, pos: usize);
+
+    fn invert(&mut self, begin: usize, end: usize);
+
+    fn swap(&mut self, first: usize, second: usize);
+
+    fn scramble(&mut self, begin: usize, order: &[usize]);
+}
@@ -0,0 +1,122 @@
+use u64s::{self, U64s, U64sStruct};
+use std::fmt;
+use gen::Gen;
+use genotype::{Genotype, LinearGenotype};
+use dominance::DominanceRule;
+use random_utils::GaRng;
+
+/// A chromosome with a single set of gene values and no dominance, so it is expressed as is
+/// and needs no decoding.
+#[derive(Clone)]
+pub struct HaploidChromosome {
+    values: U64sStruct,
+}
+
+impl fmt::Display for HaploidChromosome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let chars: Vec<char> = self.values.to_string().chars().collect();
+        let formatted: String = u64s::group_by_u64_and_byte_pos(&chars).iter().collect();
+        write!(f, "{}", formatted.trim())
+    }
+}
+
+impl fmt::Debug for HaploidChromosome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self, f)
+    }
+}
+
+impl HaploidChromosome {
+    pub fn new(values: U64sStruct) -> Self {
+        Self { values }
+    }
+
+    pub fn mutate(&mut self, pos: usize, new_gen: &Gen) {
+        let value = match *new_gen {
+            Gen::D1 | Gen::R1 => true,
+            Gen::D0 | Gen::R0 => false,
+        };
+        self.values.set(pos, value);
+    }
+}
+
+impl Genotype for HaploidChromosome {
+    fn overwrite(&mut self, source: &HaploidChromosome) {
+        self.values.overwrite(&source.values);
+    }
+    fn overwrite_with_decoded(&mut self, source: &HaploidChromosome) {
+        self.overwrite(source);
+    }
+    fn decode_genotype(&mut self) {}
+    fn decode_genotype_with<D: DominanceRule>(&mut self, _rule: &D, _rng: &mut GaRng) {}
+    fn get_decoded(&self) -> &[u64] {
+        self.values.as_slice()
+    }
+}
+
+impl LinearGenotype for HaploidChromosome {
+    fn u64s_amount(&self) -> usize {
+        self.values.u64s_amount()
+    }
+    fn cross_chromosomes(&mut self, that: &HaploidChromosome, begin: usize, amount: usize) {
+        self.values.cross_bits(&that.values, begin, amount);
+    }
+    fn cross_chromosomes_uniform(&mut self, that: &HaploidChromosome, masks: &[u64]) {
+        self.values.cross_bits_by_masks(&that.values, masks);
+    }
+    fn cross_chromosomes_k_point(&mut self, that: &HaploidChromosome, points: &[usize]) {
+        self.values.cross_bits_k_point(&that.values, points);
+    }
+    fn flip_value(&mut self, pos: usize) {
+        self.values.flip(pos);
+    }
+    fn invert(&mut self, begin: usize, end: usize) {
+        self.values.invert_bits(begin, end);
+    }
+    fn swap(&mut self, first: usize, second: usize) {
+        self.values.swap_bits(first, second);
+    }
+    fn scramble(&mut self, begin: usize, order: &[usize]) {
+        self.values.scramble_bits(begin, order);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_str(values: &str) -> HaploidChromosome {
+        HaploidChromosome::new(values.parse::<U64sStruct>().unwrap())
+    }
+
+    #[test]
+    fn decoded_must_be_values() {
+        let mut chr = from_str("1101");
+        chr.decode_genotype();
+        assert_eq!(chr.get_decoded(), &[0b1101]);
+    }
+
+    #[test]
+    fn to_string_must_group_values() {
+        assert_eq!(
+            from_str("1101").to_string(),
+            "0000 0000 0000 0000 0000 0000 0000 0000 0000 0000 0000 0000 0000 0000 0000 1101"
+        );
+    }
+
+    #[test]
+    fn cross_chromosomes_must_take_segment_from_that() {
+        let mut first = HaploidChromosome::new(U64s::new(vec![0]));
+        let second = HaploidChromosome::new(U64s::new(vec![!0u64]));
+        first.cross_chromosomes(&second, 1, 2);
+        assert_eq!(first.get_decoded(), &[0b0110]);
+    }
+
+    #[test]
+    fn mutate_must_set_expressed_value() {
+        let mut chr = from_str("1101");
+        chr.mutate(0, &Gen::D0);
+        chr.mutate(1, &Gen::R1);
+        assert_eq!(chr.get_decoded(), &[0b1110]);
+    }
+}
@@ -1,85 +1,276 @@
-use generation::Generation;
+use generation::{Generation, Selection};
 use individual::Individual;
 use breeding::Breeding;
-use random_utils::{RandomUtils, ChoosingProbability};
+use random_utils::{self, RandomUtils, GaRng};
 use fitness_calculator::FitnessCalculator;
+use fitness_cache::FitnessCache;
 use std::marker::PhantomData;
 use std;
 use generation;
 use chromosome::Chromosome;
+use genotype::Genotype;
+use ga_config::{GaConfig, Conception};
+use viability::{ViabilityPolicy, NoViabilityPolicy};
+use dominance::{DominanceRule, StandardDominance};
 
-pub struct Incubator<RU: RandomUtils, CP: ChoosingProbability, B: Breeding<RU>, FC: FitnessCalculator> {
-    new_gen: Generation<CP>,
-    old_gen: Generation<CP>,
-    _phantom_b: PhantomData<B>,
-    _phantom_fc: PhantomData<FC>,
+pub struct Incubator<
+    RU: RandomUtils,
+    S: Selection,
+    B: Breeding<RU, G>,
+    FC: FitnessCalculator,
+    G: Genotype = Chromosome,
+    D: DominanceRule = StandardDominance,
+> {
+    new_gen: Generation<G>,
+    old_gen: Generation<G>,
+    config: GaConfig,
+    rng: GaRng,
+    selection: S,
+    breeding: B,
+    fitness_calculator: FC,
+    viability_policy: Box<dyn ViabilityPolicy + Send>,
+    dominance_rule: D,
+    fitness_cache: Option<FitnessCache>,
     _phantom_ru: PhantomData<RU>,
 }
 
-impl<RU: RandomUtils, CP: ChoosingProbability, B: Breeding<RU>, FC: FitnessCalculator>
-    Incubator<RU, CP, B, FC> {
-    pub fn new(chromosomes_amount: usize) -> Self {
-        let individuals = (0..chromosomes_amount)
-            .map(|_| Incubator::<RU, CP, B, FC>::generate_individual())
-            .collect::<Vec<Individual>>();
-        Self {
-            new_gen: Generation::new(individuals.clone()),
-            old_gen: Generation::new(individuals),
-            _phantom_b: PhantomData,
-            _phantom_fc: PhantomData,
+impl<
+        RU: RandomUtils,
+        S: Selection,
+        B: Breeding<RU, G>,
+        FC: FitnessCalculator,
+        G: Genotype,
+        D: DominanceRule + Default,
+    > Incubator<RU, S, B, FC, G, D> {
+    pub fn new(
+        config: GaConfig,
+        seed: u64,
+        selection: S,
+        mut breeding: B,
+        mut fitness_calculator: FC,
+    ) -> Self {
+        config.validate();
+        let mut rng = random_utils::seeded_rng(seed);
+        let dominance_rule = D::default();
+        let individuals = (0..config.population_size)
+            .map(|_| {
+                generate_individual(
+                    &config,
+                    &mut breeding,
+                    &mut fitness_calculator,
+                    &dominance_rule,
+                    &mut rng,
+                )
+            })
+            .collect::<Vec<Individual<G>>>();
+        let mut incubator = Self {
+            new_gen: Generation::new(individuals.clone(), config.objective.clone()),
+            old_gen: Generation::new(individuals, config.objective.clone()),
+            config,
+            rng,
+            selection,
+            breeding,
+            fitness_calculator,
+            viability_policy: Box::new(NoViabilityPolicy),
+            dominance_rule,
+            fitness_cache: None,
             _phantom_ru: PhantomData,
+        };
+        incubator.update_fitness_bounds();
+        incubator
+    }
+
+    /// Every individual is viable unless a policy is set. Unviable individuals are left out of
+    /// the worst fitness, and out of breeding when the config excludes them.
+    pub fn set_viability_policy(&mut self, viability_policy: Box<dyn ViabilityPolicy + Send>) {
+        self.viability_policy = viability_policy;
+        self.update_fitness_bounds();
+    }
+
+    /// Re-expresses the current population with the new rule, so rules can be compared on it.
+    pub fn set_dominance_rule(&mut self, dominance_rule: D) {
+        self.dominance_rule = dominance_rule;
+        for individual in &mut self.new_gen.individuals {
+            individual.chromosome.invalidate_decoded();
+        }
+        for individual in &mut self.old_gen.individuals {
+            let chromosome = &mut individual.chromosome;
+            chromosome.invalidate_decoded();
+            chromosome.decode_genotype_with(&self.dominance_rule, &mut self.rng);
+            individual.fitness = self.fitness_calculator.calc_fitness(chromosome.get_decoded());
+        }
+        self.update_fitness_bounds();
+    }
+
+    /// Children whose decoded genotype is found in the cache take its fitness instead of
+    /// calling the fitness calculator. The cache starts with the current population.
+    pub fn set_fitness_cache(&mut self, mut fitness_cache: FitnessCache) {
+        for individual in &self.old_gen.individuals {
+            fitness_cache.insert(individual.chromosome.get_decoded(), individual.fitness);
         }
+        self.fitness_cache = Some(fitness_cache);
     }
 
-    pub fn get_best_individual(&self) -> Individual {
-        generation::find_best_individual(&self.old_gen.individuals)
+    pub fn get_fitness_cache(&self) -> Option<&FitnessCache> {
+        self.fitness_cache.as_ref()
     }
 
-    pub fn get_worst_individual(&self) -> Individual {
+    fn update_fitness_bounds(&mut self) {
+        self.old_gen.update_fitness_bounds(
+            &*self.viability_policy,
+            self.config.exclude_unviable_from_breeding,
+        );
+    }
+
+    pub fn get_best_individual(&self) -> Individual<G> {
+        generation::find_best_individual(&self.old_gen.individuals, &self.config.objective)
+    }
+
+    pub fn get_worst_individual(&self) -> Individual<G> {
         generation::find_worst_individual(
             &self.old_gen.individuals,
-            generation::calc_overage_fitness(&self.old_gen.individuals),
+            &self.config.objective,
+            &*self.viability_policy,
         )
     }
 
+    pub fn preserve_elite(&mut self) {
+        if self.config.elite_count == 0 {
+            return;
+        }
+        let individuals = &self.old_gen.individuals;
+        let elite_positions = generation::find_elite_positions(
+            individuals,
+            self.config.elite_count,
+            &self.config.objective,
+        );
+        for (pos, elite_pos) in elite_positions.iter().enumerate() {
+            let new_individual = &mut self.new_gen.individuals[pos];
+            let elite = &individuals[*elite_pos];
+            new_individual.chromosome.overwrite_with_decoded(&elite.chromosome);
+            new_individual.fitness = elite.fitness;
+        }
+    }
+
     pub fn create_individuals(&mut self) {
         let individuals = &self.old_gen.individuals;
-        let parents = self.old_gen.select_parent_pairs();
+        let elite_count = self.config.elite_count;
+        let children_per_pair = match self.config.conception {
+            Conception::SingleChild => 1,
+            Conception::ComplementaryPair => 2,
+        };
+        let slots = self.new_gen.individuals.len() - elite_count;
+        let parents = self.selection.select_parent_pairs(
+            &self.old_gen,
+            slots.div_ceil(children_per_pair),
+            &mut self.rng,
+        );
 
-        for (pos, pair) in parents.iter().enumerate() {
-            let new_individual = &mut self.new_gen.individuals[pos];
-            let mut child = &mut new_individual.chromosome;
+        let children_slots = self.new_gen.individuals[elite_count..].chunks_mut(children_per_pair);
+        for (children, pair) in children_slots.zip(parents.iter()) {
             let first = &individuals[pair.first_pos].chromosome;
             let second = &individuals[pair.second_pos].chromosome;
-            B::conception(first, second, &mut child);
-            child.decode_genotype();
-            new_individual.fitness = FC::calc_fitness(&child.decoded);
+            {
+                let (first_child, rest) = children.split_first_mut().unwrap();
+                match rest.first_mut() {
+                    Some(second_child) => self.breeding.conception_pair(
+                        &self.config,
+                        first,
+                        second,
+                        &mut first_child.chromosome,
+                        &mut second_child.chromosome,
+                        &mut self.rng,
+                    ),
+                    None => self.breeding.conception(
+                        &self.config,
+                        first,
+                        second,
+                        &mut first_child.chromosome,
+                        &mut self.rng,
+                    ),
+                }
+            }
+            for new_individual in children.iter_mut() {
+                let child = &mut new_individual.chromosome;
+                child.decode_genotype_with(&self.dominance_rule, &mut self.rng);
+                new_individual.fitness = match self.fitness_cache {
+                    Some(ref mut cache) => match cache.get(child.get_decoded()) {
+                        Some(fitness) => fitness,
+                        None => {
+                            let fitness = calc_child_fitness(
+                                &mut self.fitness_calculator,
+                                child,
+                                new_individual.fitness,
+                            );
+                            cache.insert(child.get_decoded(), fitness);
+                            fitness
+                        }
+                    },
+                    None => calc_child_fitness(
+                        &mut self.fitness_calculator,
+                        child,
+                        new_individual.fitness,
+                    ),
+                };
+            }
         }
     }
 
     pub fn make_next_generation(&mut self) {
+        self.preserve_elite();
         self.create_individuals();
         std::mem::swap(&mut self.new_gen, &mut self.old_gen);
-        let overage_fitness = generation::calc_overage_fitness(&self.old_gen.individuals);
-        self.old_gen.max_fitness = generation::find_best_fitness(&self.old_gen.individuals);
-        self.old_gen.min_fitness =
-            generation::find_worst_fitness(&self.old_gen.individuals, overage_fitness);
-    }
-
-    fn generate_individual() -> Individual {
-        let mut chromosome = B::generate_chromosome();
-        chromosome.decode_genotype();
-        Individual {
-            fitness: FC::calc_fitness(&chromosome.decoded),
-            chromosome,
-        }
+        self.old_gen.number = self.new_gen.number + 1;
+        self.update_fitness_bounds();
     }
 
-    pub fn get_chromosomes(&mut self) -> Vec<Chromosome> {
+    pub fn get_chromosomes(&mut self) -> Vec<G> {
         self.old_gen
             .individuals
             .iter()
             .map(|i| i.chromosome.clone())
             .collect()
     }
+
+    pub fn get_config(&self) -> &GaConfig {
+        &self.config
+    }
+
+    pub fn get_fitness_calculator(&self) -> &FC {
+        &self.fitness_calculator
+    }
+}
+
+fn calc_child_fitness<FC: FitnessCalculator, G: Genotype>(
+    fitness_calculator: &mut FC,
+    child: &G,
+    previous_fitness: f64,
+) -> f64 {
+    match child.get_decoded_changes() {
+        Some(changes) => {
+            fitness_calculator.calc_fitness_incremental(child.get_decoded(), &changes, previous_fitness)
+        }
+        None => fitness_calculator.calc_fitness(child.get_decoded()),
+    }
+}
+
+fn generate_individual<
+    RU: RandomUtils,
+    B: Breeding<RU, G>,
+    FC: FitnessCalculator,
+    G: Genotype,
+    D: DominanceRule,
+>(
+    config: &GaConfig,
+    breeding: &mut B,
+    fitness_calculator: &mut FC,
+    dominance_rule: &D,
+    rng: &mut GaRng,
+) -> Individual<G> {
+    let mut chromosome = breeding.generate_chromosome(config, rng);
+    chromosome.decode_genotype_with(dominance_rule, rng);
+    Individual {
+        fitness: fitness_calculator.calc_fitness(chromosome.get_decoded()),
+        chromosome,
+    }
 }
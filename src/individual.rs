@@ -1,20 +1,49 @@
 
 use chromosome::Chromosome;
+use genotype::Genotype;
 use std::fmt;
 
 #[derive(Clone)]
-pub struct Individual {
-    pub chromosome: Chromosome,
+pub struct Individual<G: Genotype = Chromosome> {
+    pub chromosome: G,
     pub fitness: f64,
 }
 
-impl fmt::Display for Individual {
+impl<G: Genotype> fmt::Display for Individual<G> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "\n{}\n{}\n", self.chromosome, self.fitness)
     }
 }
 
-impl fmt::Debug for Individual {
+impl<G: Genotype> fmt::Debug for Individual<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Individual of a multi-objective search, `rank` is the index of its non-dominated front.
+#[derive(Clone)]
+pub struct MultiObjectiveIndividual<G: Genotype = Chromosome> {
+    pub chromosome: G,
+    pub fitnesses: Vec<f64>,
+    pub rank: usize,
+    pub crowding_distance: f64,
+}
+
+impl<G: Genotype> MultiObjectiveIndividual<G> {
+    pub fn is_crowded_better(&self, than: &MultiObjectiveIndividual<G>) -> bool {
+        self.rank < than.rank ||
+            self.rank == than.rank && self.crowding_distance > than.crowding_distance
+    }
+}
+
+impl<G: Genotype> fmt::Display for MultiObjectiveIndividual<G> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\n{}\n{:?}\n", self.chromosome, self.fitnesses)
+    }
+}
+
+impl<G: Genotype> fmt::Debug for MultiObjectiveIndividual<G> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(self, f)
     }
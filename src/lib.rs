@@ -18,10 +18,20 @@ pub mod utils;
 pub mod gen;
 pub mod u64s;
 pub mod zygote;
+pub mod ga_config;
 pub mod random_utils;
+pub mod dominance;
+pub mod genotype;
 pub mod chromosome;
+pub mod haploid_chromosome;
+pub mod genome;
 pub mod individual;
+pub mod viability;
 pub mod generation;
+pub mod decoding;
+pub mod schema;
 pub mod fitness_calculator;
+pub mod fitness_cache;
 pub mod breeding;
 pub mod incubator;
+pub mod nsga2;
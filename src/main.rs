@@ -16,14 +16,24 @@ extern crate conv;
 pub mod utils;
 pub mod gen;
 pub mod u64s;
+pub mod ga_config;
 pub mod random_utils;
 pub mod zygote;
+pub mod dominance;
+pub mod genotype;
 pub mod chromosome;
+pub mod haploid_chromosome;
+pub mod genome;
 pub mod individual;
+pub mod viability;
 pub mod generation;
+pub mod decoding;
+pub mod schema;
 pub mod fitness_calculator;
+pub mod fitness_cache;
 pub mod breeding;
 pub mod incubator;
+pub mod nsga2;
 pub mod global_constants;
 
 use breeding::*;
@@ -34,15 +44,10 @@ use conv::*;
 use utils::*;
 use std::time::{SystemTime, UNIX_EPOCH};
 use global_constants::*;
-
-impl RandomParams for RandomParamsStruct {
-    fn chromosome_genes_amount() -> usize {
-        20 * U64_BITS_AMOUNT
-    }
-}
+use ga_config::GaConfig;
 
 impl FitnessCalculator for FitnessCalculatorStruct {
-    fn calc_fitness(decoded_genotype: &[u64]) -> f64 {
+    fn calc_fitness(&mut self, decoded_genotype: &[u64]) -> f64 {
         let u64s = decode_bits_to_u64s(decoded_genotype);
         u64s.iter().map(|l| f64::approx_from(*l)).fold(
             0.0,
@@ -55,14 +60,22 @@ impl FitnessCalculator for FitnessCalculatorStruct {
 
 
 fn main() {
-    let chromosomes_amount = 1000;
+    let config = GaConfig::new(1000, 20 * U64_BITS_AMOUNT);
+    let seed = get_ms_now();
+    println!("seed = {}", seed);
 
     let mut incubator: Incubator<
-        RandomUtilsStruct<RandomParamsStruct>,
+        RandomUtilsStruct,
+        PerfChoosingProbability,
+        BreedingStruct<RandomUtilsStruct>,
+        FitnessCalculatorStruct,
+    > = Incubator::new(
+        config,
+        seed,
         PerfChoosingProbability,
-        BreedingStruct<RandomUtilsStruct<RandomParamsStruct>>,
+        BreedingStruct::new(RandomUtilsStruct),
         FitnessCalculatorStruct,
-    > = Incubator::new(chromosomes_amount);
+    );
 
     let duration = run_and_measure(|| for _ in 0..100_000 {
         incubator.make_next_generation();
@@ -0,0 +1,327 @@
+use individual::MultiObjectiveIndividual;
+use breeding::Breeding;
+use random_utils::{self, RandomUtils, GaRng};
+use fitness_calculator::MultiObjectiveFitnessCalculator;
+use chromosome::Chromosome;
+use genotype::Genotype;
+use generation::Objective;
+use ga_config::{GaConfig, Conception};
+use dominance::{DominanceRule, StandardDominance};
+use rand::Rng;
+use std::f64;
+use std::marker::PhantomData;
+
+pub fn dominates(first: &[f64], second: &[f64], objectives: &[Objective]) -> bool {
+    let mut better_in_any = false;
+    for ((a, b), objective) in first.iter().zip(second).zip(objectives) {
+        if objective.is_better(*b, *a) {
+            return false;
+        }
+        if objective.is_better(*a, *b) {
+            better_in_any = true;
+        }
+    }
+    better_in_any
+}
+
+/// Splits positions into non-dominated fronts, the first front is the Pareto front.
+pub fn non_dominated_sort(fitnesses: &[Vec<f64>], objectives: &[Objective]) -> Vec<Vec<usize>> {
+    let n = fitnesses.len();
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut domination_counts = vec![0; n];
+    for p in 0..n {
+        for q in p + 1..n {
+            if dominates(&fitnesses[p], &fitnesses[q], objectives) {
+                dominated[p].push(q);
+                domination_counts[q] += 1;
+            } else if dominates(&fitnesses[q], &fitnesses[p], objectives) {
+                dominated[q].push(p);
+                domination_counts[p] += 1;
+            }
+        }
+    }
+    let mut fronts = Vec::new();
+    let mut front: Vec<usize> = (0..n).filter(|p| domination_counts[*p] == 0).collect();
+    while !front.is_empty() {
+        let mut next = Vec::new();
+        for p in &front {
+            for q in &dominated[*p] {
+                domination_counts[*q] -= 1;
+                if domination_counts[*q] == 0 {
+                    next.push(*q);
+                }
+            }
+        }
+        fronts.push(front);
+        front = next;
+    }
+    fronts
+}
+
+/// Crowding distance of every position of the front, boundary ones get infinity.
+pub fn crowding_distances(fitnesses: &[Vec<f64>], front: &[usize]) -> Vec<f64> {
+    let len = front.len();
+    if len <= 2 {
+        return vec![f64::INFINITY; len];
+    }
+    let mut distances = vec![0.0; len];
+    let mut order: Vec<usize> = (0..len).collect();
+    let value = |i: usize, m: usize| fitnesses[front[i]][m];
+    for m in 0..fitnesses[front[0]].len() {
+        order.sort_by(|a, b| value(*a, m).total_cmp(&value(*b, m)));
+        let range = value(order[len - 1], m) - value(order[0], m);
+        distances[order[0]] = f64::INFINITY;
+        distances[order[len - 1]] = f64::INFINITY;
+        if range > 0.0 {
+            for w in order.windows(3) {
+                distances[w[1]] += (value(w[2], m) - value(w[0], m)) / range;
+            }
+        }
+    }
+    distances
+}
+
+/// NSGA-II: offspring of parents chosen by crowded tournaments compete with the current
+/// population, and the best fronts by crowded comparison survive. Elitism comes from the
+/// survival itself, so the elite count and the exclusion of unviable individuals don't apply,
+/// and fitnesses are never cached since the cache keeps a single fitness per genotype.
+pub struct Nsga2Incubator<
+    RU: RandomUtils,
+    B: Breeding<RU, G>,
+    MFC: MultiObjectiveFitnessCalculator,
+    G: Genotype = Chromosome,
+    D: DominanceRule = StandardDominance,
+> {
+    // the population followed by the same amount of offspring slots
+    individuals: Vec<MultiObjectiveIndividual<G>>,
+    objectives: Vec<Objective>,
+    config: GaConfig,
+    rng: GaRng,
+    breeding: B,
+    fitness_calculator: MFC,
+    dominance_rule: D,
+    generation_number: usize,
+    _phantom_ru: PhantomData<RU>,
+}
+
+impl<
+        RU: RandomUtils,
+        B: Breeding<RU, G>,
+        MFC: MultiObjectiveFitnessCalculator,
+        G: Genotype,
+        D: DominanceRule + Default,
+    > Nsga2Incubator<RU, B, MFC, G, D> {
+    pub fn new(config: GaConfig, seed: u64, mut breeding: B, mut fitness_calculator: MFC) -> Self {
+        config.validate();
+        assert!(config.elite_count == 0, "NSGA-II doesn't support elite count");
+        assert!(
+            !config.exclude_unviable_from_breeding,
+            "NSGA-II doesn't support excluding unviable individuals from breeding"
+        );
+        let mut rng = random_utils::seeded_rng(seed);
+        let dominance_rule = D::default();
+        let individuals = (0..config.population_size)
+            .map(|_| {
+                let mut chromosome: G = breeding.generate_chromosome(&config, &mut rng);
+                chromosome.decode_genotype_with(&dominance_rule, &mut rng);
+                MultiObjectiveIndividual {
+                    fitnesses: fitness_calculator.calc_fitnesses(chromosome.get_decoded()),
+                    chromosome,
+                    rank: 0,
+                    crowding_distance: 0.0,
+                }
+            })
+            .collect::<Vec<MultiObjectiveIndividual<G>>>();
+        let mut incubator = Self {
+            individuals,
+            objectives: fitness_calculator.get_objectives(),
+            config,
+            rng,
+            breeding,
+            fitness_calculator,
+            dominance_rule,
+            generation_number: 0,
+            _phantom_ru: PhantomData,
+        };
+        incubator.rank_population();
+        incubator
+    }
+
+    /// Re-expresses the current population with the new rule and ranks it again.
+    pub fn set_dominance_rule(&mut self, dominance_rule: D) {
+        self.dominance_rule = dominance_rule;
+        self.individuals.truncate(self.config.population_size);
+        for individual in &mut self.individuals {
+            let chromosome = &mut individual.chromosome;
+            chromosome.invalidate_decoded();
+            chromosome.decode_genotype_with(&self.dominance_rule, &mut self.rng);
+            individual.fitnesses = self.fitness_calculator.calc_fitnesses(chromosome.get_decoded());
+        }
+        self.rank_population();
+    }
+
+    /// Ranks the population alone and fills the offspring slots with its copies.
+    fn rank_population(&mut self) {
+        self.select_survivors();
+        let offspring = self.individuals.clone();
+        self.individuals.extend(offspring);
+    }
+
+    pub fn make_next_generation(&mut self) {
+        self.create_offspring();
+        self.select_survivors();
+        self.generation_number += 1;
+    }
+
+    pub fn get_population(&self) -> &[MultiObjectiveIndividual<G>] {
+        &self.individuals[..self.config.population_size]
+    }
+
+    pub fn get_pareto_front(&self) -> Vec<MultiObjectiveIndividual<G>> {
+        self.get_population()
+            .iter()
+            .filter(|i| i.rank == 0)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_generation_number(&self) -> usize {
+        self.generation_number
+    }
+
+    pub fn get_config(&self) -> &GaConfig {
+        &self.config
+    }
+
+    pub fn get_fitness_calculator(&self) -> &MFC {
+        &self.fitness_calculator
+    }
+
+    fn create_offspring(&mut self) {
+        let children_per_pair = match self.config.conception {
+            Conception::SingleChild => 1,
+            Conception::ComplementaryPair => 2,
+        };
+        let (population, offspring) = self.individuals.split_at_mut(self.config.population_size);
+        for children in offspring.chunks_mut(children_per_pair) {
+            let first = &population[crowded_tournament(population, &mut self.rng)].chromosome;
+            let second = &population[crowded_tournament(population, &mut self.rng)].chromosome;
+            {
+                let (first_child, rest) = children.split_first_mut().unwrap();
+                match rest.first_mut() {
+                    Some(second_child) => self.breeding.conception_pair(
+                        &self.config,
+                        first,
+                        second,
+                        &mut first_child.chromosome,
+                        &mut second_child.chromosome,
+                        &mut self.rng,
+                    ),
+                    None => self.breeding.conception(
+                        &self.config,
+                        first,
+                        second,
+                        &mut first_child.chromosome,
+                        &mut self.rng,
+                    ),
+                }
+            }
+            for child in children.iter_mut() {
+                child.chromosome.decode_genotype_with(&self.dominance_rule, &mut self.rng);
+                child.fitnesses = self.fitness_calculator.calc_fitnesses(
+                    child.chromosome.get_decoded(),
+                );
+            }
+        }
+    }
+
+    /// Ranks the population together with the offspring and moves the survivors to the front.
+    fn select_survivors(&mut self) {
+        let fitnesses: Vec<Vec<f64>> = self.individuals.iter().map(|i| i.fitnesses.clone()).collect();
+        let mut order = Vec::with_capacity(self.individuals.len());
+        for (rank, front) in non_dominated_sort(&fitnesses, &self.objectives).iter().enumerate() {
+            let distances = crowding_distances(&fitnesses, front);
+            let mut front_order: Vec<usize> = (0..front.len()).collect();
+            front_order.sort_by(|a, b| distances[*b].total_cmp(&distances[*a]));
+            for i in front_order {
+                let individual = &mut self.individuals[front[i]];
+                individual.rank = rank;
+                individual.crowding_distance = distances[i];
+                order.push(front[i]);
+            }
+        }
+        let mut slots: Vec<Option<MultiObjectiveIndividual<G>>> =
+            self.individuals.drain(..).map(Some).collect();
+        self.individuals
+            .extend(order.iter().map(|p| slots[*p].take().unwrap()));
+    }
+}
+
+fn crowded_tournament<G: Genotype>(
+    population: &[MultiObjectiveIndividual<G>],
+    rng: &mut GaRng,
+) -> usize {
+    let first = rng.gen_range(0, population.len());
+    let second = rng.gen_range(0, population.len());
+    if population[second].is_crowded_better(&population[first]) {
+        second
+    } else {
+        first
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOTH_MINIMIZED: [Objective; 2] = [Objective::Minimize, Objective::Minimize];
+
+    #[test]
+    fn domination_must_require_being_better_in_any_and_worse_in_none() {
+        let objectives = [Objective::Maximize, Objective::Minimize];
+        assert!(dominates(&[2.0, 1.0], &[1.0, 1.0], &objectives));
+        assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0], &objectives));
+        assert!(!dominates(&[2.0, 2.0], &[1.0, 1.0], &objectives));
+        assert!(dominates(&[1.0, 1.0], &[1.0, 2.0], &objectives));
+    }
+
+    #[test]
+    fn fronts_must_follow_domination() {
+        let fitnesses = vec![
+            vec![3.0, 3.0],
+            vec![1.0, 4.0],
+            vec![2.0, 2.0],
+            vec![4.0, 1.0],
+            vec![4.0, 4.0],
+        ];
+        assert_eq!(
+            non_dominated_sort(&fitnesses, &BOTH_MINIMIZED),
+            vec![vec![1, 2, 3], vec![0], vec![4]]
+        );
+    }
+
+    #[test]
+    fn crowding_distance_must_favour_isolated_points() {
+        let fitnesses = vec![
+            vec![0.0, 4.0],
+            vec![1.0, 3.0],
+            vec![1.5, 2.5],
+            vec![4.0, 0.0],
+        ];
+        let distances = crowding_distances(&fitnesses, &[0, 1, 2, 3]);
+        assert_eq!(distances[0], f64::INFINITY);
+        assert_eq!(distances[3], f64::INFINITY);
+        assert_eq!(distances[1], 1.5 / 4.0 * 2.0);
+        assert_eq!(distances[2], 3.0 / 4.0 * 2.0);
+    }
+
+    #[test]
+    fn nan_fitness_must_be_dominated_and_not_panic() {
+        let fitnesses = vec![vec![f64::NAN, 1.0], vec![1.0, 1.0], vec![0.0, 2.0]];
+        assert_eq!(
+            non_dominated_sort(&fitnesses, &BOTH_MINIMIZED),
+            vec![vec![1, 2], vec![0]]
+        );
+        assert_eq!(crowding_distances(&fitnesses, &[0, 1, 2]).len(), 3);
+    }
+}
@@ -1,94 +1,127 @@
 use gen::Gen;
 use zygote::Zygote;
-use rand;
+use rand::{Rng, SeedableRng, StdRng};
 use conv::ValueFrom;
-use std::marker::PhantomData;
 use u64s::U64s;
+use ga_config::{GaConfig, SegmentLength};
+
+pub type GaRng = StdRng;
+
+pub fn seeded_rng(seed: u64) -> GaRng {
+    StdRng::from_seed(&[seed as usize, (seed >> 32) as usize][..])
+}
 
 pub trait RandomUtils {
-    fn mutation_pos() -> usize;
+    fn mutation_pos(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize;
 
-    fn crossing_chromosome_pos() -> usize;
+    fn crossing_chromosome_pos(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize;
 
-    fn crossing_zygote_pos() -> usize;
+    fn crossing_segment_length(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize;
+
+    fn crossing_mask(&mut self, rng: &mut GaRng) -> u64 {
+        rng.gen::<u64>()
+    }
 
-    fn should_cross_zygotes() -> bool;
+    fn crossing_zygote_pos(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize;
 
-    fn should_mutate() -> bool;
+    fn gamete_from_recessive(&mut self, rng: &mut GaRng) -> bool {
+        rng.gen::<bool>()
+    }
 
-    fn rand_gen() -> Gen;
+    fn chromosome_from_second_parent(&mut self, rng: &mut GaRng) -> bool {
+        rng.gen::<bool>()
+    }
 
-    fn generate_zygote() -> Zygote;
-}
+    fn should_cross_chromosomes(&mut self, config: &GaConfig, rng: &mut GaRng) -> bool;
 
-pub struct RandomUtilsStruct<R>
-where
-    R: RandomParams,
-{
-    _p: PhantomData<R>,
-}
+    fn should_cross_zygotes(&mut self, config: &GaConfig, rng: &mut GaRng) -> bool;
 
-pub struct RandomParamsStruct;
+    fn should_mutate(&mut self, config: &GaConfig, rng: &mut GaRng) -> bool;
 
-pub trait RandomParams {
-    fn chromosome_genes_amount() -> usize;
+    fn should_mutate_with_rate(&mut self, config: &GaConfig, rate: f64, rng: &mut GaRng) -> bool {
+        rng.gen::<f64>() < f64::value_from(config.chromosome_genes_amount).unwrap() * rate
+    }
+
+    fn scramble_order(&mut self, amount: usize, rng: &mut GaRng) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..amount).collect();
+        rng.shuffle(&mut order);
+        order
+    }
+
+    fn rand_gen(&mut self, rng: &mut GaRng) -> Gen;
+
+    fn generate_zygote(&mut self, config: &GaConfig, rng: &mut GaRng) -> Zygote;
 }
 
-impl<R> RandomUtils for RandomUtilsStruct<R>
-where
-    R: RandomParams,
-{
-    fn mutation_pos() -> usize {
-        rand::random::<usize>() % R::chromosome_genes_amount()
+pub struct RandomUtilsStruct;
+
+impl RandomUtils for RandomUtilsStruct {
+    fn mutation_pos(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize {
+        rng.gen::<usize>() % config.chromosome_genes_amount
+    }
+
+    fn crossing_chromosome_pos(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize {
+        rng.gen::<usize>() % config.chromosome_genes_amount
+    }
+
+    fn crossing_segment_length(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize {
+        match config.crossing_segment_length {
+            SegmentLength::UpToChromosomeLength => {
+                rng.gen::<usize>() % config.chromosome_genes_amount
+            }
+            SegmentLength::Uniform { min, max } => rng.gen_range(min, max + 1),
+            SegmentLength::Fixed(length) => length,
+        }
     }
 
-    fn crossing_chromosome_pos() -> usize {
-        rand::random::<usize>() % R::chromosome_genes_amount()
+    fn crossing_zygote_pos(&mut self, config: &GaConfig, rng: &mut GaRng) -> usize {
+        rng.gen::<usize>() % config.chromosome_genes_amount
     }
 
-    fn crossing_zygote_pos() -> usize {
-        rand::random::<usize>() % R::chromosome_genes_amount()
+    fn should_cross_chromosomes(&mut self, config: &GaConfig, rng: &mut GaRng) -> bool {
+        rng.gen::<f64>() < config.chromosomes_crossing_probability
     }
 
-    fn should_cross_zygotes() -> bool {
-        rand::random::<f64>() <
-            f64::value_from(R::chromosome_genes_amount()).unwrap() * 2f64 / 1_000_000.0
+    fn should_cross_zygotes(&mut self, config: &GaConfig, rng: &mut GaRng) -> bool {
+        rng.gen::<f64>() <
+            f64::value_from(config.chromosome_genes_amount).unwrap() * config.zygotes_crossing_rate
     }
 
-    fn should_mutate() -> bool {
-        rand::random::<f64>() < f64::value_from(R::chromosome_genes_amount()).unwrap() / 10_000.0f64
+    fn should_mutate(&mut self, config: &GaConfig, rng: &mut GaRng) -> bool {
+        self.should_mutate_with_rate(config, config.mutation_rate, rng)
     }
 
-    fn rand_gen() -> Gen {
-        rand::random::<Gen>()
+    fn rand_gen(&mut self, rng: &mut GaRng) -> Gen {
+        rng.gen::<Gen>()
     }
 
-    fn generate_zygote() -> Zygote {
-        let len = R::chromosome_genes_amount() / 64;
-        let d = (0..len).map(|_| rand::random::<u64>()).collect();
-        let v = (0..len).map(|_| rand::random::<u64>()).collect();
-        let dominance = U64s::new(d);
-        let values = U64s::new(v);
+    fn generate_zygote(&mut self, config: &GaConfig, rng: &mut GaRng) -> Zygote {
+        let genes_amount = config.chromosome_genes_amount;
+        let len = genes_amount.div_ceil(64);
+        let d = (0..len).map(|_| rng.gen::<u64>()).collect();
+        let v = (0..len).map(|_| rng.gen::<u64>()).collect();
+        let dominance = U64s::with_bits_amount(d, genes_amount);
+        let values = U64s::with_bits_amount(v, genes_amount);
         Zygote::new(dominance, values)
     }
 }
 
 pub trait ChoosingProbability {
-    fn select_individual_probability(fitness: f64) -> bool;
+    fn select_individual_probability(&self, fitness: f64, rng: &mut GaRng) -> bool;
 }
 
 pub struct RandomChoosingProbability;
 
 impl ChoosingProbability for RandomChoosingProbability {
-    fn select_individual_probability(fitness: f64) -> bool {
-        fitness > rand::random::<f64>()
+    fn select_individual_probability(&self, fitness: f64, rng: &mut GaRng) -> bool {
+        fitness > rng.gen::<f64>()
     }
 }
 
 pub struct PerfChoosingProbability;
 
 impl ChoosingProbability for PerfChoosingProbability {
-    fn select_individual_probability(_fitness: f64) -> bool {
+    fn select_individual_probability(&self, _fitness: f64, _rng: &mut GaRng) -> bool {
         true
     }
 }
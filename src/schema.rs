@@ -0,0 +1,246 @@
+use decoding::{extract_bits, Encoding, RealVariable};
+use genotype::Genotype;
+use individual::Individual;
+use global_constants::U64_BITS_AMOUNT;
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub enum VariableKind {
+    Integer(Encoding),
+    Real(RealVariable),
+    Boolean,
+    /// The bits are read as a binary index wrapped around the amount of categories.
+    Categorical(Vec<String>),
+}
+
+#[derive(Clone, Debug)]
+pub struct SchemaVariable {
+    pub name: String,
+    pub width: usize,
+    pub kind: VariableKind,
+}
+
+impl SchemaVariable {
+    pub fn integer(name: &str, width: usize, encoding: Encoding) -> Self {
+        Self::new(name, width, VariableKind::Integer(encoding))
+    }
+
+    pub fn real(name: &str, variable: RealVariable) -> Self {
+        let width = variable.width;
+        Self::new(name, width, VariableKind::Real(variable))
+    }
+
+    pub fn boolean(name: &str) -> Self {
+        Self::new(name, 1, VariableKind::Boolean)
+    }
+
+    pub fn categorical(name: &str, width: usize, categories: &[&str]) -> Self {
+        let categories = categories.iter().map(|c| c.to_string()).collect();
+        Self::new(name, width, VariableKind::Categorical(categories))
+    }
+
+    fn new(name: &str, width: usize, kind: VariableKind) -> Self {
+        Self {
+            name: name.to_string(),
+            width,
+            kind,
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.width > U64_BITS_AMOUNT {
+            return Err(format!("width of '{}' must be within [1, 64]", self.name));
+        }
+        match self.kind {
+            VariableKind::Integer(ref encoding)
+                if !encoding.is_signed() && self.width == U64_BITS_AMOUNT =>
+            {
+                Err(format!("unsigned integer '{}' must not be wider than 63 bits", self.name))
+            }
+            VariableKind::Categorical(ref categories)
+                if categories.is_empty()
+                    || (self.width < U64_BITS_AMOUNT && categories.len() > 1 << self.width) =>
+            {
+                Err(format!("'{}' must have from 1 to 2^width categories", self.name))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn decode(&self, bits: u64) -> Value {
+        match self.kind {
+            VariableKind::Integer(ref encoding) => {
+                Value::Integer(encoding.decode_integer(bits, self.width))
+            }
+            VariableKind::Real(ref variable) => Value::Real(variable.map(bits)),
+            VariableKind::Boolean => Value::Boolean(bits == 1),
+            VariableKind::Categorical(ref categories) => {
+                Value::Categorical(categories[(bits % categories.len() as u64) as usize].clone())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    Real(f64),
+    Boolean(bool),
+    Categorical(String),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Integer(v) => write!(f, "{}", v),
+            Value::Real(v) => write!(f, "{}", v),
+            Value::Boolean(v) => write!(f, "{}", v),
+            Value::Categorical(ref v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Named values in the order the variables are declared in the schema.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Phenotype {
+    pub values: Vec<(String, Value)>,
+}
+
+impl Phenotype {
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.iter().find(|v| v.0 == name).map(|v| &v.1)
+    }
+}
+
+impl fmt::Display for Phenotype {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values: Vec<String> = self.values
+            .iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect();
+        write!(f, "{}", values.join(", "))
+    }
+}
+
+/// Declares consecutive named variables over the decoded genotype starting from its lowest bit.
+pub struct Schema {
+    variables: Vec<SchemaVariable>,
+}
+
+impl Schema {
+    pub fn new(variables: Vec<SchemaVariable>, genes_amount: usize) -> Result<Self, String> {
+        for (i, variable) in variables.iter().enumerate() {
+            variable.validate()?;
+            if variables[..i].iter().any(|v| v.name == variable.name) {
+                return Err(format!("variable '{}' is declared twice", variable.name));
+            }
+        }
+        let bits_amount: usize = variables.iter().map(|v| v.width).sum();
+        if bits_amount > genes_amount {
+            return Err(format!(
+                "variables take {} bits but the chromosome has only {} genes",
+                bits_amount,
+                genes_amount
+            ));
+        }
+        Ok(Self { variables })
+    }
+
+    pub fn bits_amount(&self) -> usize {
+        self.variables.iter().map(|v| v.width).sum()
+    }
+
+    pub fn decode_bits(&self, decoded: &[u64]) -> Phenotype {
+        let mut from = 0;
+        let mut values = Vec::with_capacity(self.variables.len());
+        for variable in &self.variables {
+            let bits = extract_bits(decoded, from, variable.width);
+            values.push((variable.name.clone(), variable.decode(bits)));
+            from += variable.width;
+        }
+        Phenotype { values }
+    }
+
+    pub fn decode<G: Genotype>(&self, individual: &Individual<G>) -> Phenotype {
+        self.decode_bits(individual.chromosome.get_decoded())
+    }
+
+    /// Same layout as `Individual`'s `Display` but with the phenotype instead of the genes.
+    pub fn report<G: Genotype>(&self, individual: &Individual<G>) -> String {
+        format!("\n{}\n{}\n", self.decode(individual), individual.fitness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chromosome::Chromosome;
+    use zygote::Zygote;
+
+    fn schema() -> Schema {
+        Schema::new(
+            vec![
+                SchemaVariable::integer("count", 4, Encoding::TwosComplement),
+                SchemaVariable::real("ratio", RealVariable::new(2, 0.0, 3.0)),
+                SchemaVariable::boolean("enabled"),
+                SchemaVariable::categorical("color", 2, &["red", "green", "blue"]),
+            ],
+            U64_BITS_AMOUNT,
+        ).unwrap()
+    }
+
+    #[test]
+    fn bits_must_be_decoded_into_named_values() {
+        let phenotype = schema().decode_bits(&[0b11 << 7 | 1 << 6 | 0b10 << 4 | 0b1110]);
+        assert_eq!(phenotype.get("count"), Some(&Value::Integer(-2)));
+        assert_eq!(phenotype.get("ratio"), Some(&Value::Real(2.0)));
+        assert_eq!(phenotype.get("enabled"), Some(&Value::Boolean(true)));
+        assert_eq!(phenotype.get("color"), Some(&Value::Categorical("red".to_string())));
+        assert_eq!(phenotype.get("absent"), None);
+    }
+
+    #[test]
+    fn individual_must_be_reported_with_phenotype() {
+        let zygote = |s: &str| s.parse::<Zygote>().unwrap();
+        let mut chromosome = Chromosome::new(zygote("DDDd Dddd ddDd"), zygote("rrrr rrrr rrrr"));
+        chromosome.decode_genotype();
+        let individual = Individual {
+            chromosome,
+            fitness: 1.5,
+        };
+        assert_eq!(
+            schema().report(&individual),
+            "\ncount = 2, ratio = 0, enabled = false, color = green\n1.5\n"
+        );
+    }
+
+    #[test]
+    fn schema_must_not_exceed_genes_amount() {
+        assert!(Schema::new(vec![SchemaVariable::integer("a", 60, Encoding::Binary)], 64).is_ok());
+        assert!(
+            Schema::new(
+                vec![
+                    SchemaVariable::integer("a", 60, Encoding::Binary),
+                    SchemaVariable::integer("b", 5, Encoding::Binary),
+                ],
+                64
+            ).is_err()
+        );
+    }
+
+    #[test]
+    fn invalid_variables_must_be_rejected() {
+        let invalid = vec![
+            SchemaVariable::integer("a", 0, Encoding::Binary),
+            SchemaVariable::integer("a", 64, Encoding::Gray),
+            SchemaVariable::categorical("a", 1, &["x", "y", "z"]),
+            SchemaVariable::categorical("a", 1, &[]),
+        ];
+        for variable in invalid {
+            assert!(Schema::new(vec![variable], 128).is_err());
+        }
+        assert!(
+            Schema::new(vec![SchemaVariable::boolean("a"), SchemaVariable::boolean("a")], 64).is_err()
+        );
+    }
+}
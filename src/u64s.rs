@@ -1,12 +1,29 @@
 use std::fmt;
 use std::str;
 use std;
+use utils::width_mask;
 
 pub trait U64s {
     fn cross_bits(&mut self, that: &Self, from: usize, amount: usize);
 
     fn cross_bits_bidirectional(&mut self, that: &mut Self, from: usize, amount: usize);
 
+    fn cross_bits_by_masks(&mut self, that: &Self, masks: &[u64]);
+
+    fn cross_bits_by_masks_bidirectional(&mut self, that: &mut Self, masks: &[u64]);
+
+    fn cross_bits_k_point(&mut self, that: &Self, points: &[usize]);
+
+    fn flip(&mut self, pos: usize);
+
+    fn swap_bits(&mut self, first: usize, second: usize);
+
+    fn invert_bits(&mut self, begin: usize, end: usize);
+
+    fn scramble_bits(&mut self, begin: usize, order: &[usize]);
+
+    fn as_slice(&self) -> &[u64];
+
     fn overwrite(&mut self, source: &U64sStruct);
 
     fn get(&self, pos: usize) -> bool;
@@ -21,7 +38,13 @@ pub trait U64s {
 
     fn get_u64(&self, p: usize) -> u64;
 
+    fn bits_amount(&self) -> usize;
+
+    fn last_u64_mask(&self) -> u64;
+
     fn new(u64s: Vec<u64>) -> Self;
+
+    fn with_bits_amount(u64s: Vec<u64>, bits_amount: usize) -> Self;
 }
 
 impl fmt::Display for U64sStruct {
@@ -32,7 +55,7 @@ impl fmt::Display for U64sStruct {
             let aligned = format!("{:0>1$}", binary, 64);
             string = format!("{}{}", aligned, string);
         }
-        write!(f, "{}", string)
+        write!(f, "{}", &string[string.len() - self.bits_amount..])
     }
 }
 
@@ -64,7 +87,7 @@ impl str::FromStr for U64sStruct {
             u64s.push(bits_to_u(formatted.as_str()));
             filtered = splitted.1.to_string();
         }
-        Ok(U64sStruct { u64s: u64s.to_vec() })
+        Ok(U64sStruct::new(u64s))
     }
 }
 
@@ -82,14 +105,35 @@ fn bits_to_u(s: &str) -> u64 {
     num
 }
 
+/// Bits past `bits_amount` in the last word are kept zeroed.
 #[derive(Clone)]
 pub struct U64sStruct {
     u64s: Vec<u64>,
+    bits_amount: usize,
 }
 
 impl U64s for U64sStruct {
     fn new(u64s: Vec<u64>) -> Self {
-        Self { u64s }
+        let bits_amount = u64s.len() * 64;
+        Self { u64s, bits_amount }
+    }
+
+    fn with_bits_amount(u64s: Vec<u64>, bits_amount: usize) -> Self {
+        assert_eq!(u64s.len(), bits_amount.div_ceil(64), "words must hold exactly the bits");
+        let mut u64s_struct = Self { u64s, bits_amount };
+        if !u64s_struct.is_empty() {
+            let mask = u64s_struct.last_u64_mask();
+            *u64s_struct.u64s.last_mut().unwrap() &= mask;
+        }
+        u64s_struct
+    }
+
+    fn bits_amount(&self) -> usize {
+        self.bits_amount
+    }
+
+    fn last_u64_mask(&self) -> u64 {
+        width_mask(self.bits_amount - (self.u64s.len() - 1) * 64)
     }
 
     fn overwrite(&mut self, source: &U64sStruct) {
@@ -178,6 +222,64 @@ impl U64s for U64sStruct {
             };
         }
     }
+
+    fn cross_bits_by_masks(&mut self, that: &U64sStruct, masks: &[u64]) {
+        for (p, mask) in masks.iter().enumerate() {
+            self.u64s[p] = self.u64s[p] & !mask | that.u64s[p] & mask;
+        }
+    }
+
+    fn cross_bits_by_masks_bidirectional(&mut self, that: &mut U64sStruct, masks: &[u64]) {
+        for (p, mask) in masks.iter().enumerate() {
+            let int_to = self.u64s[p];
+            let int_from = that.u64s[p];
+            self.u64s[p] = int_to & !mask | int_from & mask;
+            that.u64s[p] = int_from & !mask | int_to & mask;
+        }
+    }
+
+    fn cross_bits_k_point(&mut self, that: &U64sStruct, points: &[usize]) {
+        for segment in points.chunks(2) {
+            let amount = if segment.len() == 2 {
+                segment[1] - segment[0]
+            } else {
+                usize::MAX - segment[0]
+            };
+            self.cross_bits(that, segment[0], amount);
+        }
+    }
+
+    fn flip(&mut self, pos: usize) {
+        self.u64s[pos / 64] ^= u64_mask(pos);
+    }
+
+    fn swap_bits(&mut self, first: usize, second: usize) {
+        let first_bit = self.get(first);
+        let second_bit = self.get(second);
+        self.set(first, second_bit);
+        self.set(second, first_bit);
+    }
+
+    fn invert_bits(&mut self, begin: usize, end: usize) {
+        let (mut left, mut right) = (begin, end);
+        while left + 1 < right {
+            right -= 1;
+            self.swap_bits(left, right);
+            left += 1;
+        }
+    }
+
+    fn scramble_bits(&mut self, begin: usize, order: &[usize]) {
+        let bits: Vec<bool> = (0..order.len()).map(|i| self.get(begin + i)).collect();
+        for (i, o) in order.iter().enumerate() {
+            self.set(begin + i, bits[*o]);
+        }
+    }
+
+    fn as_slice(&self) -> &[u64] {
+        &self.u64s
+    }
+
     fn get(&self, pos: usize) -> bool {
         (self.u64s[pos / 64] & u64_mask(pos)) != 0
     }
@@ -291,56 +393,22 @@ fn cross_last_u64_bidirectional(
     array_from[pos] = int_from & !mask | last_masked_to;
 }
 
-trait Grouped
-where
-    Self: std::marker::Sized,
-{
-    fn grouped(&self, p: usize) -> Vec<Self>;
-}
-
-impl Grouped for Vec<char> {
-    fn grouped(&self, p: usize) -> Vec<Vec<char>> {
-        let mut vec = Vec::new();
-        let mut pos = 0;
-        let mut gathered = Vec::new();
-        for &ch in self {
-            if pos == p {
-                vec.push(gathered.clone());
-                gathered = Vec::new();
-                gathered.push(ch);
-                pos = 1;
-            } else {
-                gathered.push(ch);
-                pos += 1;
-            }
+/// Groups are aligned to the end of the string where the lowest bit is, so a partial
+/// word or byte comes first.
+pub fn group_by_u64_and_byte_pos(s: &[char]) -> Vec<char> {
+    let len = s.len();
+    let mut grouped = Vec::with_capacity(len + len / 4 + len / 64);
+    for (i, &ch) in s.iter().enumerate() {
+        grouped.push(ch);
+        let rest = len - i - 1;
+        if rest.is_multiple_of(4) {
+            grouped.push(' ');
+        }
+        if rest.is_multiple_of(64) {
+            grouped.push(' ');
         }
-        vec.push(gathered);
-
-        vec
     }
-}
-
-
-pub fn group_by_u64_and_byte_pos(s: &[char]) -> Vec<char> {
-    let new_str = s.to_vec().grouped(64).iter().fold(
-        Vec::new(),
-        |mut acc, v| {
-            let b = v.grouped(4).into_iter().fold(Vec::new(), |mut acc2, v2| {
-                acc2.push(v2);
-                acc2.push(vec![' ']);
-                acc2
-            });
-            acc.append(&mut b.clone());
-            acc.append(&mut vec![vec![' ']]);
-            acc
-        },
-    );
-    let mut new_vec = Vec::new();
-    for v in new_str {
-        let mut cp = v.clone();
-        new_vec.append(&mut cp);
-    }
-    new_vec
+    grouped
 }
 
 #[cfg(test)]
@@ -519,6 +587,91 @@ mod tests {
     }
 }
 
+#[cfg(test)]
+mod crossing_by_masks {
+    use super::*;
+
+    #[test]
+    fn must_take_masked_bits_from_that() {
+        let mut to = U64sStruct::new(vec![0, !0u64]);
+        let from = U64sStruct::new(vec![!0u64, 0]);
+        to.cross_bits_by_masks(&from, &[0b1010, 0b0110]);
+        assert_eq!(to.get_u64(0), 0b1010);
+        assert_eq!(to.get_u64(1), !0b0110u64);
+    }
+
+    #[test]
+    fn bidirectional_must_swap_masked_bits() {
+        let mut to = U64sStruct::new(vec![0b1100]);
+        let mut from = U64sStruct::new(vec![0b0011]);
+        to.cross_bits_by_masks_bidirectional(&mut from, &[0b0110]);
+        assert_eq!(to.get_u64(0), 0b1010);
+        assert_eq!(from.get_u64(0), 0b0101);
+    }
+}
+
+#[cfg(test)]
+mod rearranging_bits {
+    use super::*;
+
+    #[test]
+    fn k_point_must_alternate_segments() {
+        let mut to = U64sStruct::new(vec![0]);
+        let from = U64sStruct::new(vec![!0u64]);
+        to.cross_bits_k_point(&from, &[1, 3, 60]);
+        assert_eq!(to.get_u64(0), 0xF000_0000_0000_0006);
+    }
+
+    #[test]
+    fn flip_must_change_only_one_bit() {
+        let mut u = U64sStruct::new(vec![0b1010, 0]);
+        u.flip(1);
+        u.flip(64);
+        assert_eq!(u.as_slice(), &[0b1000, 1]);
+    }
+
+    #[test]
+    fn invert_and_scramble_must_reorder_bits_in_range() {
+        let mut u = U64sStruct::new(vec![0b0011]);
+        u.invert_bits(0, 4);
+        assert_eq!(u.get_u64(0), 0b1100);
+        u.scramble_bits(1, &[2, 0, 1]);
+        assert_eq!(u.get_u64(0), 0b1010);
+    }
+}
+
+#[cfg(test)]
+mod exact_length {
+    use super::*;
+
+    #[test]
+    fn tail_bits_must_be_masked_and_hidden() {
+        let u = U64sStruct::with_bits_amount(vec![!0, !0], 65);
+        assert_eq!(u.as_slice(), &[!0, 1]);
+        assert_eq!(u.last_u64_mask(), 1);
+        assert_eq!(u.to_string().len(), 65);
+        assert_eq!(U64sStruct::with_bits_amount(vec![0b101], 3).to_string(), "101");
+    }
+
+    #[test]
+    fn crossing_must_stop_at_last_bit() {
+        let mut to = U64sStruct::with_bits_amount(vec![0, 0], 70);
+        let mut from = U64sStruct::new(vec![!0, !0]);
+        to.cross_bits(&from, 60, usize::MAX - 60);
+        assert_eq!(to.as_slice(), &[0xF000_0000_0000_0000, 0b11_1111]);
+        let mut to = U64sStruct::with_bits_amount(vec![0, 0], 70);
+        to.cross_bits_bidirectional(&mut from, 66, 100);
+        assert_eq!(to.as_slice(), &[0, 0b11_1100]);
+    }
+
+    #[test]
+    fn odd_lengths_must_be_grouped_from_lowest_bit() {
+        let chars: Vec<char> = "111110000".chars().collect();
+        let grouped: String = group_by_u64_and_byte_pos(&chars).iter().collect();
+        assert_eq!(grouped.trim(), "1 1111 0000");
+    }
+}
+
 struct CopyParams {
     first_bits_amount: usize,
     p_byte_from: usize,
@@ -540,7 +693,7 @@ struct CrossParams {
 
 impl U64sStruct {
     fn calc_copy_params(&self, from: usize, amount: usize) -> CopyParams {
-        let size = self.u64s.len() * 64;
+        let size = self.bits_amount;
         let n_from = if from > size { size } else { from };
         let n_bits_amount = if size - n_from < amount {
             size - n_from
@@ -1,3 +1,6 @@
+use conv::*;
+use global_constants::U64_BITS_AMOUNT;
+
 pub fn normalize_fitness(fitness: f64, min_fitness: f64, max_fitness: f64) -> f64 {
     let based_fitness = fitness - min_fitness;
     let fitness_range = max_fitness - min_fitness;
@@ -25,6 +28,32 @@ pub fn decode_bits_to_u64s(bits: &[u64]) -> Vec<u64> {
     bits.iter().map(gray2bin).collect()
 }
 
+pub fn width_mask(width: usize) -> u64 {
+    if width < U64_BITS_AMOUNT {
+        (1u64 << width) - 1
+    } else {
+        !0
+    }
+}
+
+pub fn gray_to_bin(bits: u64, width: usize) -> u64 {
+    gray2bin(&(bits & width_mask(width)))
+}
+
+pub fn twos_complement_to_i64(bits: u64, width: usize) -> i64 {
+    let shift = U64_BITS_AMOUNT - width;
+    ((bits << shift) as i64) >> shift
+}
+
+pub fn sign_magnitude_to_i64(bits: u64, width: usize) -> i64 {
+    let magnitude = i64::value_from(bits & width_mask(width - 1)).unwrap();
+    if (bits >> (width - 1)) & 1 == 1 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
 #[cfg(test)]
 mod gray_to_bin {
     use super::*;
@@ -42,6 +71,36 @@ mod gray_to_bin {
     }
 }
 
+#[cfg(test)]
+mod decoding_of_width {
+    use super::*;
+
+    #[test]
+    fn gray_must_be_decoded_for_any_width() {
+        assert_eq!(gray_to_bin(0b110, 3), 0b100);
+        assert_eq!(gray_to_bin(0b1111_0110, 3), 0b100);
+        assert_eq!(gray_to_bin(1, 1), 1);
+        assert_eq!(gray_to_bin(1 << 63, 64), !0);
+    }
+
+    #[test]
+    fn twos_complement_must_extend_sign() {
+        assert_eq!(twos_complement_to_i64(0b011, 3), 3);
+        assert_eq!(twos_complement_to_i64(0b100, 3), -4);
+        assert_eq!(twos_complement_to_i64(0b111, 3), -1);
+        assert_eq!(twos_complement_to_i64(1, 1), -1);
+        assert_eq!(twos_complement_to_i64(!0, 64), -1);
+    }
+
+    #[test]
+    fn sign_magnitude_must_take_sign_from_highest_bit() {
+        assert_eq!(sign_magnitude_to_i64(0b011, 3), 3);
+        assert_eq!(sign_magnitude_to_i64(0b111, 3), -3);
+        assert_eq!(sign_magnitude_to_i64(0b100, 3), 0);
+        assert_eq!(sign_magnitude_to_i64(!0, 64), -i64::MAX);
+    }
+}
+
 #[cfg(test)]
 mod normalize_fitness {
     use super::*;
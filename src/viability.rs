@@ -0,0 +1,195 @@
+use generation::Objective;
+use conv::*;
+use std::cmp::Ordering;
+
+pub trait ViabilityPolicy {
+    fn find_viable(&self, fitnesses: &[f64], objective: &Objective) -> Vec<bool>;
+}
+
+pub struct NoViabilityPolicy;
+
+impl ViabilityPolicy for NoViabilityPolicy {
+    fn find_viable(&self, fitnesses: &[f64], _objective: &Objective) -> Vec<bool> {
+        vec![true; fitnesses.len()]
+    }
+}
+
+/// Treats an individual as unviable when its distance from the best fitness is more than
+/// `ratio` times the average distance, so it stays meaningful for zero and negative fitness.
+pub struct RatioThresholdPolicy {
+    ratio: f64,
+}
+
+impl RatioThresholdPolicy {
+    pub fn new(ratio: f64) -> Self {
+        assert!(ratio >= 0.0, "ratio threshold must not be negative");
+        Self { ratio }
+    }
+}
+
+impl ViabilityPolicy for RatioThresholdPolicy {
+    fn find_viable(&self, fitnesses: &[f64], objective: &Objective) -> Vec<bool> {
+        let oriented = orient_fitnesses(fitnesses, objective);
+        let finite = filter_finite(&oriented);
+        let best = finite.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let overage_distance = best - calc_overage(&finite);
+        oriented
+            .iter()
+            .map(|f| best - f <= overage_distance * self.ratio)
+            .collect()
+    }
+}
+
+pub struct ZScorePolicy {
+    threshold: f64,
+}
+
+impl ZScorePolicy {
+    pub fn new(threshold: f64) -> Self {
+        Self { threshold }
+    }
+}
+
+impl ViabilityPolicy for ZScorePolicy {
+    fn find_viable(&self, fitnesses: &[f64], objective: &Objective) -> Vec<bool> {
+        let oriented = orient_fitnesses(fitnesses, objective);
+        let finite = filter_finite(&oriented);
+        let overage = calc_overage(&finite);
+        let deviations: Vec<f64> = finite.iter().map(|f| (f - overage).powi(2)).collect();
+        let standard_deviation = calc_overage(&deviations).sqrt();
+        oriented
+            .iter()
+            .map(|f| {
+                standard_deviation == 0.0 || (overage - f) / standard_deviation <= self.threshold
+            })
+            .collect()
+    }
+}
+
+pub struct PercentileClippingPolicy {
+    percentile: f64,
+}
+
+impl PercentileClippingPolicy {
+    pub fn new(percentile: f64) -> Self {
+        assert!(
+            (0.0..100.0).contains(&percentile),
+            "percentile must be within [0, 100)"
+        );
+        Self { percentile }
+    }
+}
+
+impl ViabilityPolicy for PercentileClippingPolicy {
+    fn find_viable(&self, fitnesses: &[f64], objective: &Objective) -> Vec<bool> {
+        let mut sorted = fitnesses.to_vec();
+        sorted.sort_by(|a, b| objective.compare(*a, *b));
+        let last_pos = f64::value_from(sorted.len() - 1).unwrap();
+        let cutoff_pos: usize = (last_pos * self.percentile / 100.0)
+            .approx_by::<RoundToZero>()
+            .unwrap();
+        let cutoff = sorted[cutoff_pos];
+        fitnesses
+            .iter()
+            .map(|f| objective.compare(*f, cutoff) != Ordering::Less)
+            .collect()
+    }
+}
+
+fn orient_fitnesses(fitnesses: &[f64], objective: &Objective) -> Vec<f64> {
+    fitnesses.iter().map(|f| objective.orient(*f)).collect()
+}
+
+fn filter_finite(values: &[f64]) -> Vec<f64> {
+    values.iter().cloned().filter(|v| v.is_finite()).collect()
+}
+
+fn calc_overage(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / f64::value_from(values.len()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_policy_must_keep_everybody() {
+        assert_eq!(
+            NoViabilityPolicy.find_viable(&[0.0, -1e9, 1e9], &Objective::Maximize),
+            vec![true, true, true]
+        );
+    }
+
+    #[test]
+    fn ratio_threshold_must_work_with_zero_and_negative_fitness() {
+        let policy = RatioThresholdPolicy::new(2.0);
+        let fitnesses = &[0.0, -1.0, 1.0, 0.5, -100.0];
+        assert_eq!(
+            policy.find_viable(fitnesses, &Objective::Maximize),
+            vec![true, true, true, true, false]
+        );
+        assert_eq!(
+            policy.find_viable(fitnesses, &Objective::Minimize),
+            vec![true, true, true, true, true]
+        );
+    }
+
+    #[test]
+    fn non_finite_fitness_must_not_make_others_unviable() {
+        let fitnesses = &[10.0, 9.0, f64::NAN, 10.0, -50.0];
+        let expected = vec![true, true, false, true, false];
+        assert_eq!(
+            RatioThresholdPolicy::new(2.0).find_viable(fitnesses, &Objective::Maximize),
+            expected
+        );
+        assert_eq!(
+            ZScorePolicy::new(1.5).find_viable(fitnesses, &Objective::Maximize),
+            expected
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "ratio threshold must not be negative")]
+    fn negative_ratio_threshold_must_be_rejected() {
+        RatioThresholdPolicy::new(-1.0);
+    }
+
+    #[test]
+    fn z_score_must_drop_only_bad_outliers() {
+        let policy = ZScorePolicy::new(1.5);
+        let fitnesses = &[10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, -50.0];
+        assert_eq!(
+            policy.find_viable(fitnesses, &Objective::Maximize),
+            vec![true, true, true, true, true, true, true, true, false]
+        );
+        assert!(
+            policy
+                .find_viable(fitnesses, &Objective::Minimize)
+                .iter()
+                .all(|v| *v)
+        );
+    }
+
+    #[test]
+    fn percentile_clipping_must_treat_nan_as_the_worst() {
+        let policy = PercentileClippingPolicy::new(25.0);
+        assert_eq!(
+            policy.find_viable(&[2.0, f64::NAN, 1.0, 3.0, 4.0], &Objective::Minimize),
+            vec![true, false, true, true, true]
+        );
+    }
+
+    #[test]
+    fn percentile_clipping_must_drop_the_worst_share() {
+        let policy = PercentileClippingPolicy::new(50.0);
+        let fitnesses = &[5.0, 1.0, 4.0, 2.0, 3.0];
+        assert_eq!(
+            policy.find_viable(fitnesses, &Objective::Maximize),
+            vec![true, false, true, false, true]
+        );
+        assert_eq!(
+            policy.find_viable(fitnesses, &Objective::Minimize),
+            vec![false, true, false, true, true]
+        );
+    }
+}
@@ -88,10 +88,45 @@ impl Zygote {
         self.dominance.u64s_amount()
     }
 
+    pub fn genes_amount(&self) -> usize {
+        self.dominance.bits_amount()
+    }
+
+    pub fn last_u64_mask(&self) -> u64 {
+        self.dominance.last_u64_mask()
+    }
+
     pub fn mutate(&mut self, pos: usize, new_gen: &Gen) {
         self.set(pos, new_gen);
     }
 
+    pub fn flip_dominance(&mut self, pos: usize) {
+        self.dominance.flip(pos);
+    }
+
+    pub fn flip_value(&mut self, pos: usize) {
+        self.values.flip(pos);
+    }
+
+    pub fn invert(&mut self, begin: usize, end: usize) {
+        self.dominance.invert_bits(begin, end);
+        self.values.invert_bits(begin, end);
+    }
+
+    pub fn swap(&mut self, first: usize, second: usize) {
+        self.dominance.swap_bits(first, second);
+        self.values.swap_bits(first, second);
+    }
+
+    pub fn scramble(&mut self, begin: usize, order: &[usize]) {
+        self.dominance.scramble_bits(begin, order);
+        self.values.scramble_bits(begin, order);
+    }
+
+    pub fn into_values(self) -> U64sStruct {
+        self.values
+    }
+
     fn set(&mut self, pos: usize, gen: &Gen) {
         match *gen {
             Gen::D1 => {
@@ -139,6 +174,27 @@ impl Zygote {
         self.dominance.cross_bits(&that.dominance, begin, amount);
         self.values.cross_bits(&that.values, begin, amount);
     }
+
+    pub fn cross_k_point(&mut self, that: &Zygote, points: &[usize]) {
+        self.dominance.cross_bits_k_point(&that.dominance, points);
+        self.values.cross_bits_k_point(&that.values, points);
+    }
+
+    pub fn cross_by_masks(&mut self, that: &Zygote, masks: &[u64]) {
+        self.dominance.cross_bits_by_masks(&that.dominance, masks);
+        self.values.cross_bits_by_masks(&that.values, masks);
+    }
+
+    pub fn cross_by_masks_bidirectional(&mut self, that: &mut Zygote, masks: &[u64]) {
+        self.dominance.cross_bits_by_masks_bidirectional(
+            &mut that.dominance,
+            masks,
+        );
+        self.values.cross_bits_by_masks_bidirectional(
+            &mut that.values,
+            masks,
+        );
+    }
 }
 
 
@@ -318,6 +374,21 @@ mod tests {
         )
     }
 
+    #[test]
+    fn cross_k_point_must_alternate_segments() {
+        let mut zgt1 = Zygote::from_str(
+            "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd",
+        ).unwrap();
+        let zgt2 = Zygote::from_str(
+            "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr",
+        ).unwrap();
+        zgt1.cross_k_point(&zgt2, &[2, 5, 9]);
+        assert_eq!(
+            zgt1.to_string(),
+            "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrd dddr rrdd"
+        );
+    }
+
     #[test]
     fn cross_parts_when_end_pos_bigger_than_size() {
         let crossed: Result<Zygote, String> =
@@ -333,3 +404,53 @@ mod tests {
         )
     }
 }
+
+#[cfg(test)]
+mod mutation_operators {
+    use super::*;
+    use std::str::FromStr;
+
+    fn lowest_genes(zgt: &Zygote) -> String {
+        let s = zgt.to_string();
+        s[s.len() - 4..].to_string()
+    }
+
+    #[test]
+    fn flip_dominance_must_keep_value() {
+        let mut zgt = Zygote::from_str("rRdD").unwrap();
+        zgt.flip_dominance(0);
+        zgt.flip_dominance(3);
+        assert_eq!(lowest_genes(&zgt), "dRdR");
+    }
+
+    #[test]
+    fn flip_value_must_keep_dominance() {
+        let mut zgt = Zygote::from_str("rRdD").unwrap();
+        zgt.flip_value(1);
+        zgt.flip_value(2);
+        assert_eq!(lowest_genes(&zgt), "rrDD");
+    }
+
+    #[test]
+    fn invert_must_reverse_genes_order_in_range() {
+        let mut zgt = Zygote::from_str("rRdD").unwrap();
+        zgt.invert(0, 4);
+        assert_eq!(lowest_genes(&zgt), "DdRr");
+        zgt.invert(1, 3);
+        assert_eq!(lowest_genes(&zgt), "DRdr");
+    }
+
+    #[test]
+    fn swap_must_exchange_two_genes() {
+        let mut zgt = Zygote::from_str("rRdD").unwrap();
+        zgt.swap(0, 3);
+        assert_eq!(lowest_genes(&zgt), "DRdr");
+    }
+
+    #[test]
+    fn scramble_must_reorder_genes_in_range() {
+        let mut zgt = Zygote::from_str("rRdD").unwrap();
+        zgt.scramble(0, &[2, 0, 1]);
+        assert_eq!(lowest_genes(&zgt), "rdDR");
+    }
+}
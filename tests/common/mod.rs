@@ -0,0 +1,121 @@
+#![allow(dead_code)]
+
+use genetic_algorithm::random_utils::{RandomUtilsStruct, RandomChoosingProbability};
+use genetic_algorithm::fitness_calculator::{FitnessCalculator, MultiObjectiveFitnessCalculator};
+use genetic_algorithm::breeding::{Breeding, BreedingStruct};
+use genetic_algorithm::chromosome::Chromosome;
+use genetic_algorithm::genotype::{Genotype, DecodedChanges};
+use genetic_algorithm::incubator::Incubator;
+use genetic_algorithm::nsga2::Nsga2Incubator;
+use genetic_algorithm::dominance::{DominanceRule, StandardDominance};
+use genetic_algorithm::ga_config::GaConfig;
+
+pub const CHROMOSOMES_AMOUNT: usize = 10;
+pub const GENERATIONS_AMOUNT: usize = 50;
+
+pub fn count_ones(words: &[u64]) -> f64 {
+    words.iter().map(|l| f64::from(l.count_ones())).sum()
+}
+
+pub struct FitnessCalculatorMock;
+
+impl FitnessCalculator for FitnessCalculatorMock {
+    fn calc_fitness(&mut self, decoded_genotype: &[u64]) -> f64 {
+        count_ones(decoded_genotype)
+    }
+}
+
+/// Counts ones like `FitnessCalculatorMock`, keeping track of the calls.
+#[derive(Default)]
+pub struct CountingFitnessCalculatorMock {
+    pub full_calls: usize,
+    pub incremental_calls: usize,
+    pub changed_words: usize,
+}
+
+impl FitnessCalculator for CountingFitnessCalculatorMock {
+    fn calc_fitness(&mut self, decoded_genotype: &[u64]) -> f64 {
+        self.full_calls += 1;
+        count_ones(decoded_genotype)
+    }
+
+    fn calc_fitness_incremental(
+        &mut self,
+        decoded_genotype: &[u64],
+        changes: &DecodedChanges,
+        previous_fitness: f64,
+    ) -> f64 {
+        self.incremental_calls += 1;
+        self.changed_words += changes.words.len();
+        let current: Vec<u64> = changes.words.iter().map(|p| decoded_genotype[*p]).collect();
+        previous_fitness - count_ones(changes.previous_words) + count_ones(&current)
+    }
+}
+
+pub type TestIncubator<FC, G = Chromosome> = Incubator<
+    RandomUtilsStruct,
+    RandomChoosingProbability,
+    BreedingStruct<RandomUtilsStruct>,
+    FC,
+    G,
+>;
+
+pub type MockIncubator<G = Chromosome> = TestIncubator<FitnessCalculatorMock, G>;
+
+pub type CountingIncubator = TestIncubator<CountingFitnessCalculatorMock>;
+
+pub fn incubator_with<FC: FitnessCalculator, G: Genotype>(
+    config: GaConfig,
+    seed: u64,
+    fitness_calculator: FC,
+) -> TestIncubator<FC, G>
+where
+    BreedingStruct<RandomUtilsStruct>: Breeding<RandomUtilsStruct, G>,
+{
+    Incubator::new(
+        config,
+        seed,
+        RandomChoosingProbability,
+        BreedingStruct::new(RandomUtilsStruct),
+        fitness_calculator,
+    )
+}
+
+pub fn incubator<G: Genotype>(config: GaConfig, seed: u64) -> MockIncubator<G>
+where
+    BreedingStruct<RandomUtilsStruct>: Breeding<RandomUtilsStruct, G>,
+{
+    incubator_with(config, seed, FitnessCalculatorMock)
+}
+
+pub fn counting_incubator(config: GaConfig, seed: u64) -> CountingIncubator {
+    incubator_with(config, seed, CountingFitnessCalculatorMock::default())
+}
+
+pub type TestNsga2Incubator<MFC, D = StandardDominance> =
+    Nsga2Incubator<RandomUtilsStruct, BreedingStruct<RandomUtilsStruct>, MFC, Chromosome, D>;
+
+pub fn nsga2_incubator<MFC: MultiObjectiveFitnessCalculator, D: DominanceRule + Default>(
+    config: GaConfig,
+    seed: u64,
+    fitness_calculator: MFC,
+) -> TestNsga2Incubator<MFC, D> {
+    Nsga2Incubator::new(
+        config,
+        seed,
+        BreedingStruct::new(RandomUtilsStruct),
+        fitness_calculator,
+    )
+}
+
+pub fn best_fitness<G: Genotype>(incubator: &mut MockIncubator<G>) -> f64
+where
+    BreedingStruct<RandomUtilsStruct>: Breeding<RandomUtilsStruct, G>,
+{
+    let mut fitness_calculator = FitnessCalculatorMock;
+    incubator
+        .get_chromosomes()
+        .iter()
+        .map(|chr| fitness_calculator.calc_fitness(chr.get_decoded()))
+        .fold(0.0, f64::max)
+}
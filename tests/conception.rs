@@ -0,0 +1,147 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::random_utils::{self, RandomUtilsStruct};
+use genetic_algorithm::ga_config::{GaConfig, Crossover, Conception, Reproduction};
+use genetic_algorithm::breeding::{Breeding, BreedingStruct};
+use genetic_algorithm::chromosome::Chromosome;
+use genetic_algorithm::zygote::Zygote;
+use genetic_algorithm::global_constants::*;
+use common::MockIncubator;
+
+const DOMINANT: &str =
+    "dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd dddd";
+const SECOND_DOMINANT: &str =
+    "DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD DDDD";
+const SECOND_RECESSIVE: &str =
+    "rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr rrrr";
+const RECESSIVE: &str =
+    "RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR RRRR";
+
+fn chromosome(genes: &str) -> Chromosome {
+    Chromosome::new(
+        genes.parse::<Zygote>().unwrap(),
+        genes.parse::<Zygote>().unwrap(),
+    )
+}
+
+fn swap_parents(genes: String) -> String {
+    genes
+        .chars()
+        .map(|c| match c {
+            'd' => 'R',
+            'R' => 'd',
+            c => c,
+        })
+        .collect()
+}
+
+fn conceive_pair(crossover: Crossover) -> (Chromosome, Chromosome) {
+    let mut config = GaConfig::new(2, U64_BITS_AMOUNT);
+    config.crossover = crossover;
+    config.mutation_rate = 0.0;
+    config.zygotes_crossing_rate = 0.0;
+    let mut breeding = BreedingStruct::new(RandomUtilsStruct);
+    let mut rng = random_utils::seeded_rng(42);
+    let mut first_child = chromosome(DOMINANT);
+    let mut second_child = chromosome(DOMINANT);
+    breeding.conception_pair(
+        &config,
+        &chromosome(DOMINANT),
+        &chromosome(RECESSIVE),
+        &mut first_child,
+        &mut second_child,
+        &mut rng,
+    );
+    (first_child, second_child)
+}
+
+#[test]
+fn pair_conception_must_produce_complementary_children() {
+    for crossover in &[Crossover::Segment, Crossover::Uniform, Crossover::KPoint(3)] {
+        let (first_child, second_child) = conceive_pair(crossover.clone());
+        assert_eq!(
+            swap_parents(first_child.to_string()),
+            second_child.to_string()
+        );
+    }
+}
+
+#[test]
+fn meiosis_must_give_complementary_gametes_to_pair_of_children() {
+    let mut config = GaConfig::new(2, U64_BITS_AMOUNT);
+    config.reproduction = Reproduction::Meiosis;
+    config.mutation_rate = 0.0;
+    config.zygotes_crossing_rate = 0.0;
+    let mut breeding = BreedingStruct::new(RandomUtilsStruct);
+    let mut rng = random_utils::seeded_rng(42);
+    let first_parent = Chromosome::new(
+        DOMINANT.parse::<Zygote>().unwrap(),
+        RECESSIVE.parse::<Zygote>().unwrap(),
+    );
+    let second_parent = Chromosome::new(
+        SECOND_DOMINANT.parse::<Zygote>().unwrap(),
+        SECOND_RECESSIVE.parse::<Zygote>().unwrap(),
+    );
+    let mut first_child = chromosome(DOMINANT);
+    let mut second_child = chromosome(DOMINANT);
+    breeding.conception_pair(
+        &config,
+        &first_parent,
+        &second_parent,
+        &mut first_child,
+        &mut second_child,
+        &mut rng,
+    );
+    let first_child = first_child.to_string();
+    let second_child = second_child.to_string();
+    let mut first_lines = first_child.lines();
+    let mut second_lines = second_child.lines();
+    assert_eq!(
+        swap_parents(first_lines.next().unwrap().to_string()),
+        second_lines.next().unwrap()
+    );
+    let complement: String = first_lines
+        .next()
+        .unwrap()
+        .chars()
+        .map(|c| match c {
+            'D' => 'r',
+            'r' => 'D',
+            c => c,
+        })
+        .collect();
+    assert_eq!(complement, second_lines.next().unwrap());
+}
+
+#[test]
+fn meiosis_must_cross_zygotes_of_child() {
+    let mut config = GaConfig::new(2, U64_BITS_AMOUNT);
+    config.reproduction = Reproduction::Meiosis;
+    config.mutation_rate = 0.0;
+    config.zygotes_crossing_rate = 1.0;
+    let mut breeding = BreedingStruct::new(RandomUtilsStruct);
+    let mut rng = random_utils::seeded_rng(42);
+    let mut child = chromosome(DOMINANT);
+    breeding.conception(
+        &config,
+        &chromosome(DOMINANT),
+        &chromosome(SECOND_RECESSIVE),
+        &mut child,
+        &mut rng,
+    );
+    assert!(child.to_string().lines().next().unwrap().contains('r'));
+}
+
+#[test]
+fn pair_conception_must_fill_odd_amount_of_slots() {
+    let mut config = GaConfig::new(7, 2 * U64_BITS_AMOUNT);
+    config.conception = Conception::ComplementaryPair;
+    config.elite_count = 2;
+    let mut incubator: MockIncubator = common::incubator(config, 42);
+    for _ in 0..10 {
+        incubator.make_next_generation();
+    }
+    assert_eq!(incubator.get_chromosomes().len(), 7);
+}
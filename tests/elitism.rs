@@ -0,0 +1,30 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::ga_config::{GaConfig, SegmentLength};
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+#[test]
+fn best_fitness_must_never_regress_when_elite_is_preserved() {
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, U64_BITS_AMOUNT);
+    config.elite_count = 2;
+    config.mutation_rate = 1.0 / 64.0;
+    let mut incubator: MockIncubator = common::incubator(config, 7);
+    let mut previous_best = best_fitness(&mut incubator);
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+        let best = best_fitness(&mut incubator);
+        assert!(best >= previous_best);
+        previous_best = best;
+    }
+}
+
+#[test]
+#[should_panic(expected = "uniform segment length must have min <= max")]
+fn inverted_uniform_segment_length_must_be_rejected() {
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, U64_BITS_AMOUNT);
+    config.crossing_segment_length = SegmentLength::Uniform { min: 5, max: 4 };
+    let _: MockIncubator = common::incubator(config, 7);
+}
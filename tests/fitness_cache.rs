@@ -0,0 +1,35 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::fitness_cache::FitnessCache;
+use genetic_algorithm::genotype::Genotype;
+use genetic_algorithm::ga_config::GaConfig;
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+#[test]
+fn copies_of_known_genotypes_must_not_be_recalculated() {
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, U64_BITS_AMOUNT);
+    config.chromosomes_crossing_probability = 0.2;
+    let mut incubator = counting_incubator(config, 42);
+    incubator.set_fitness_cache(FitnessCache::new(100));
+    assert_eq!(incubator.get_fitness_cache().unwrap().len(), CHROMOSOMES_AMOUNT);
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+    }
+    let cache = incubator.get_fitness_cache().unwrap();
+    assert_eq!(
+        cache.get_hits() + cache.get_misses(),
+        (CHROMOSOMES_AMOUNT * GENERATIONS_AMOUNT) as u64
+    );
+    assert!(cache.get_hits() > cache.get_misses());
+    assert!(cache.len() <= 100);
+    let calculator = incubator.get_fitness_calculator();
+    assert_eq!(
+        (calculator.full_calls + calculator.incremental_calls) as u64,
+        CHROMOSOMES_AMOUNT as u64 + cache.get_misses()
+    );
+    let best = incubator.get_best_individual();
+    assert_eq!(best.fitness, count_ones(best.chromosome.get_decoded()));
+}
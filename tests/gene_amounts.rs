@@ -0,0 +1,80 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::random_utils::RandomUtilsStruct;
+use genetic_algorithm::breeding::{Breeding, BreedingStruct};
+use genetic_algorithm::chromosome::Chromosome;
+use genetic_algorithm::haploid_chromosome::HaploidChromosome;
+use genetic_algorithm::genotype::Genotype;
+use genetic_algorithm::ga_config::{GaConfig, Crossover, Reproduction};
+use common::*;
+
+const GENES_AMOUNTS: [usize; 4] = [1, 63, 65, 1000];
+
+fn config(genes_amount: usize, crossover: Crossover) -> GaConfig {
+    let rate = 1.0 / genes_amount as f64;
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, genes_amount);
+    config.crossover = crossover;
+    config.mutation_rate = rate;
+    config.recessive_mutation_rate = rate;
+    config.dominance_flip_rate = rate;
+    config.value_flip_rate = rate;
+    config.inversion_rate = rate;
+    config.swap_rate = rate;
+    config.scramble_rate = rate;
+    config.zygotes_crossing_rate = rate;
+    config
+}
+
+fn evolve<G: Genotype>(config: GaConfig) -> Vec<G>
+where
+    BreedingStruct<RandomUtilsStruct>: Breeding<RandomUtilsStruct, G>,
+{
+    let mut incubator: MockIncubator<G> = common::incubator(config, 42);
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+    }
+    incubator.get_chromosomes()
+}
+
+fn assert_exact_length<G: Genotype>(genotype: &G, genes_amount: usize) {
+    for zygote in genotype.to_string().lines() {
+        assert_eq!(zygote.chars().filter(|c| !c.is_whitespace()).count(), genes_amount);
+    }
+    let decoded = genotype.get_decoded();
+    assert_eq!(decoded.len(), genes_amount.div_ceil(64));
+    let ones: u32 = decoded.iter().map(|u| u.count_ones()).sum();
+    assert!(ones as usize <= genes_amount);
+    let tail = genes_amount % 64;
+    if tail > 0 {
+        assert_eq!(decoded[decoded.len() - 1] >> tail, 0);
+    }
+}
+
+#[test]
+fn diploid_chromosomes_must_keep_exact_gene_amount() {
+    for &genes_amount in &GENES_AMOUNTS {
+        for crossover in [Crossover::Segment, Crossover::Uniform, Crossover::KPoint(3)] {
+            for chromosome in evolve::<Chromosome>(config(genes_amount, crossover)) {
+                assert_exact_length(&chromosome, genes_amount);
+            }
+        }
+        let mut meiosis = config(genes_amount, Crossover::Segment);
+        meiosis.reproduction = Reproduction::Meiosis;
+        for chromosome in evolve::<Chromosome>(meiosis) {
+            assert_exact_length(&chromosome, genes_amount);
+        }
+    }
+}
+
+#[test]
+fn haploid_chromosomes_must_keep_exact_gene_amount() {
+    for &genes_amount in &GENES_AMOUNTS {
+        for crossover in [Crossover::Segment, Crossover::Uniform, Crossover::KPoint(3)] {
+            for chromosome in evolve::<HaploidChromosome>(config(genes_amount, crossover)) {
+                assert_exact_length(&chromosome, genes_amount);
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::random_utils::{self, RandomUtilsStruct};
+use genetic_algorithm::breeding::{Breeding, BreedingStruct};
+use genetic_algorithm::chromosome::Chromosome;
+use genetic_algorithm::zygote::Zygote;
+use genetic_algorithm::genome::Genome;
+use genetic_algorithm::genotype::Genotype;
+use genetic_algorithm::ga_config::GaConfig;
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+fn genome(genes: char, chromosomes_amount: usize) -> Genome {
+    let zygote: String = (0..U64_BITS_AMOUNT).map(|_| genes).collect();
+    Genome::new(
+        (0..chromosomes_amount)
+            .map(|_| {
+                Chromosome::new(
+                    zygote.parse::<Zygote>().unwrap(),
+                    zygote.parse::<Zygote>().unwrap(),
+                )
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn chromosomes_must_keep_their_own_lengths() {
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, U64_BITS_AMOUNT);
+    config.genome_genes_amounts = vec![U64_BITS_AMOUNT, 3 * U64_BITS_AMOUNT, 2 * U64_BITS_AMOUNT];
+    let mut incubator: MockIncubator<Genome> = common::incubator(config, 42);
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+    }
+    for genome in incubator.get_chromosomes() {
+        assert_eq!(genome.get_chromosome_decoded(0).len(), 1);
+        assert_eq!(genome.get_chromosome_decoded(1).len(), 3);
+        assert_eq!(genome.get_chromosome_decoded(2).len(), 2);
+        assert_eq!(genome.get_decoded().len(), 6);
+    }
+}
+
+#[test]
+fn chromosomes_must_assort_independently() {
+    let mut config = GaConfig::new(2, U64_BITS_AMOUNT);
+    config.genome_genes_amounts = vec![U64_BITS_AMOUNT; 8];
+    config.chromosomes_crossing_probability = 0.0;
+    config.mutation_rate = 0.0;
+    config.zygotes_crossing_rate = 0.0;
+    let mut breeding = BreedingStruct::new(RandomUtilsStruct);
+    let mut rng = random_utils::seeded_rng(42);
+    let mut child = genome('d', 8);
+    breeding.conception(&config, &genome('d', 8), &genome('R', 8), &mut child, &mut rng);
+    let from_first = child
+        .chromosomes
+        .iter()
+        .filter(|c| c.to_string().starts_with('d'))
+        .count();
+    assert!(from_first > 0 && from_first < 8);
+}
+
+#[test]
+fn chromosome_configs_must_follow_the_config() {
+    let mut config = GaConfig::new(2, U64_BITS_AMOUNT);
+    config.chromosome_genes_amount = 2 * U64_BITS_AMOUNT;
+    let mut breeding = BreedingStruct::new(RandomUtilsStruct);
+    let mut rng = random_utils::seeded_rng(42);
+    let genome: Genome = breeding.generate_chromosome(&config, &mut rng);
+    assert_eq!(genome.get_chromosome_decoded(0).len(), 2);
+    config.genome_genes_amounts = vec![3 * U64_BITS_AMOUNT];
+    let genome: Genome = breeding.generate_chromosome(&config, &mut rng);
+    assert_eq!(genome.get_chromosome_decoded(0).len(), 3);
+}
@@ -0,0 +1,29 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::haploid_chromosome::HaploidChromosome;
+use genetic_algorithm::ga_config::{GaConfig, Crossover};
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+#[test]
+fn haploid_population_must_evolve_with_the_same_breeding() {
+    for crossover in &[Crossover::Segment, Crossover::Uniform, Crossover::KPoint(3)] {
+        let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, 2 * U64_BITS_AMOUNT);
+        config.crossover = crossover.clone();
+        config.elite_count = 2;
+        config.mutation_rate = 1.0 / 64.0;
+        let mut incubator: MockIncubator<HaploidChromosome> = common::incubator(config, 7);
+        let initial_best = best_fitness(&mut incubator);
+        let mut previous_best = initial_best;
+        for _ in 0..GENERATIONS_AMOUNT {
+            incubator.make_next_generation();
+            let best = best_fitness(&mut incubator);
+            assert!(best >= previous_best);
+            previous_best = best;
+        }
+        assert!(previous_best > initial_best);
+        assert_eq!(incubator.get_chromosomes().len(), CHROMOSOMES_AMOUNT);
+    }
+}
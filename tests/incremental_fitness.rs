@@ -0,0 +1,38 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::genotype::Genotype;
+use genetic_algorithm::ga_config::{GaConfig, Conception};
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+#[test]
+fn incremental_fitness_must_match_full_calculation() {
+    for conception in [Conception::SingleChild, Conception::ComplementaryPair] {
+        let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, 40 * U64_BITS_AMOUNT);
+        config.conception = conception;
+        config.elite_count = 2;
+        config.chromosomes_crossing_probability = 0.5;
+        config.mutation_rate = 1.0 / 1000.0;
+        let mut incubator = counting_incubator(config, 42);
+        for _ in 0..GENERATIONS_AMOUNT {
+            incubator.make_next_generation();
+        }
+        let calculator = incubator.get_fitness_calculator();
+        assert_eq!(calculator.full_calls, CHROMOSOMES_AMOUNT);
+        assert_eq!(
+            calculator.incremental_calls,
+            (CHROMOSOMES_AMOUNT - 2) * GENERATIONS_AMOUNT
+        );
+        assert!(calculator.changed_words < calculator.incremental_calls * 40 / 2);
+        for chromosome in incubator.get_chromosomes() {
+            let mut decoded = chromosome.clone();
+            decoded.invalidate_decoded();
+            decoded.decode_genotype();
+            assert_eq!(chromosome.get_decoded(), decoded.get_decoded());
+        }
+        let best = incubator.get_best_individual();
+        assert_eq!(best.fitness, count_ones(best.chromosome.get_decoded()));
+    }
+}
@@ -0,0 +1,99 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::fitness_calculator::MultiObjectiveFitnessCalculator;
+use genetic_algorithm::decoding::{RealDecoder, RealVariable};
+use genetic_algorithm::generation::Objective;
+use genetic_algorithm::genotype::Genotype;
+use genetic_algorithm::nsga2;
+use genetic_algorithm::ga_config::{GaConfig, Conception};
+use genetic_algorithm::dominance::{DominanceRule, StandardDominance, DominantOneWins};
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+/// Schaffer's first problem: both objectives are minimized and the Pareto set is x in [0, 2].
+pub struct Schaffer {
+    decoder: RealDecoder,
+}
+
+impl Schaffer {
+    fn x(&self, decoded_genotype: &[u64]) -> f64 {
+        self.decoder.decode(decoded_genotype)[0]
+    }
+}
+
+impl MultiObjectiveFitnessCalculator for Schaffer {
+    fn get_objectives(&self) -> Vec<Objective> {
+        vec![Objective::Minimize, Objective::Minimize]
+    }
+
+    fn calc_fitnesses(&mut self, decoded_genotype: &[u64]) -> Vec<f64> {
+        let x = self.x(decoded_genotype);
+        vec![x * x, (x - 2.0) * (x - 2.0)]
+    }
+}
+
+fn config() -> GaConfig {
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, U64_BITS_AMOUNT);
+    config.mutation_rate = 1.0 / 64.0;
+    config
+}
+
+type SchafferIncubator<D = StandardDominance> = TestNsga2Incubator<Schaffer, D>;
+
+fn schaffer_incubator<D: DominanceRule + Default>(config: GaConfig) -> SchafferIncubator<D> {
+    let decoder = RealDecoder::new(vec![RealVariable::new(32, -10.0, 10.0)]);
+    nsga2_incubator(config, 42, Schaffer { decoder })
+}
+
+#[test]
+fn pareto_front_must_approach_optimal_trade_off() {
+    let mut incubator: SchafferIncubator = schaffer_incubator(config());
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+    }
+    assert_eq!(incubator.get_population().len(), CHROMOSOMES_AMOUNT);
+    let front = incubator.get_pareto_front();
+    assert!(front.len() > CHROMOSOMES_AMOUNT / 2);
+    let schaffer = incubator.get_fitness_calculator();
+    for individual in &front {
+        let x = schaffer.x(individual.chromosome.get_decoded());
+        assert!(x > -0.1 && x < 2.1, "x = {}", x);
+        for other in &front {
+            assert!(!nsga2::dominates(
+                &other.fitnesses,
+                &individual.fitnesses,
+                &schaffer.get_objectives(),
+            ));
+        }
+    }
+    let xs: Vec<f64> = front
+        .iter()
+        .map(|i| schaffer.x(i.chromosome.get_decoded()))
+        .collect();
+    let spread = xs.iter().cloned().fold(f64::MIN, f64::max) -
+        xs.iter().cloned().fold(f64::MAX, f64::min);
+    assert!(spread > 1.0, "spread = {}", spread);
+}
+
+#[test]
+fn complementary_pairs_must_fill_the_population_with_any_dominance_rule() {
+    let mut config = config();
+    config.conception = Conception::ComplementaryPair;
+    let mut incubator: SchafferIncubator<DominantOneWins> = schaffer_incubator(config);
+    incubator.set_dominance_rule(DominantOneWins);
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+    }
+    assert_eq!(incubator.get_population().len(), CHROMOSOMES_AMOUNT);
+    assert!(!incubator.get_pareto_front().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "NSGA-II doesn't support excluding unviable individuals")]
+fn excluding_unviable_individuals_must_be_rejected() {
+    let mut config = config();
+    config.exclude_unviable_from_breeding = true;
+    let _: SchafferIncubator = schaffer_incubator(config);
+}
@@ -0,0 +1,58 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::fitness_calculator::{RealFitnessCalculator, RealFitnessCalculatorAdapter};
+use genetic_algorithm::decoding::{RealDecoder, RealVariable};
+use genetic_algorithm::generation::Objective;
+use genetic_algorithm::ga_config::GaConfig;
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+pub struct Sphere;
+
+impl RealFitnessCalculator for Sphere {
+    fn calc_fitness(&mut self, variables: &[f64]) -> f64 {
+        variables.iter().map(|v| v * v).sum()
+    }
+}
+
+#[test]
+fn fitness_must_be_calculated_from_real_variables() {
+    let decoder = RealDecoder::new(vec![
+        RealVariable::new(10, -5.0, 5.0),
+        RealVariable::new(16, -5.0, 5.0),
+        RealVariable::new(23, -5.0, 5.0),
+    ]);
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, U64_BITS_AMOUNT);
+    config.objective = Objective::Minimize;
+    config.elite_count = 1;
+    config.mutation_rate = 1.0 / 64.0;
+    let fitness_calculator = RealFitnessCalculatorAdapter::new(decoder, Sphere, U64_BITS_AMOUNT);
+    let mut incubator: TestIncubator<_> =
+        incubator_with(config, 42, fitness_calculator.unwrap());
+    let initial_best = incubator.get_best_individual().fitness;
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+    }
+    let best = incubator.get_best_individual();
+    assert!(best.fitness < initial_best);
+    let variables = incubator.get_fitness_calculator().get_decoder().decode(
+        &best.chromosome.decoded,
+    );
+    assert_eq!(variables.len(), 3);
+    assert!(variables.iter().all(|v| *v >= -5.0 && *v <= 5.0));
+    assert_eq!(Sphere.calc_fitness(&variables), best.fitness);
+}
+
+#[test]
+fn variables_must_fit_into_the_chromosome() {
+    let decoder = || {
+        RealDecoder::new(vec![
+            RealVariable::new(32, -5.0, 5.0),
+            RealVariable::new(33, -5.0, 5.0),
+        ])
+    };
+    assert!(RealFitnessCalculatorAdapter::new(decoder(), Sphere, U64_BITS_AMOUNT).is_err());
+    assert!(RealFitnessCalculatorAdapter::new(decoder(), Sphere, 65).is_ok());
+}
@@ -0,0 +1,53 @@
+extern crate genetic_algorithm;
+
+mod common;
+
+use genetic_algorithm::ga_config::{GaConfig, Crossover};
+use genetic_algorithm::global_constants::*;
+use common::*;
+
+fn run_generations(seed: u64, crossover: Crossover) -> Vec<String> {
+    let mut config = GaConfig::new(CHROMOSOMES_AMOUNT, 2 * U64_BITS_AMOUNT);
+    config.crossover = crossover;
+    let mut incubator: MockIncubator = common::incubator(config, seed);
+    for _ in 0..GENERATIONS_AMOUNT {
+        incubator.make_next_generation();
+    }
+    incubator
+        .get_chromosomes()
+        .iter()
+        .map(|chr| chr.to_string())
+        .collect()
+}
+
+#[test]
+fn runs_with_the_same_seed_must_produce_identical_populations() {
+    assert_eq!(
+        run_generations(42, Crossover::Segment),
+        run_generations(42, Crossover::Segment)
+    );
+    assert_eq!(
+        run_generations(42, Crossover::Uniform),
+        run_generations(42, Crossover::Uniform)
+    );
+    assert_eq!(
+        run_generations(42, Crossover::KPoint(3)),
+        run_generations(42, Crossover::KPoint(3))
+    );
+}
+
+#[test]
+fn runs_with_different_seeds_must_produce_different_populations() {
+    assert_ne!(
+        run_generations(42, Crossover::Segment),
+        run_generations(43, Crossover::Segment)
+    );
+}
+
+#[test]
+fn seeds_differing_only_in_high_half_must_produce_different_populations() {
+    assert_ne!(
+        run_generations(42, Crossover::Segment),
+        run_generations(42 | 1 << 32, Crossover::Segment)
+    );
+}